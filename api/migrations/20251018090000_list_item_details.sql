@@ -0,0 +1,12 @@
+-- Table: ingredients
+
+ALTER TABLE public.ingredients
+    ADD IF NOT EXISTS quantity DOUBLE PRECISION,
+    ADD IF NOT EXISTS unit VARCHAR(64);
+
+-- Table: list_items
+
+ALTER TABLE public.list_items
+    ADD IF NOT EXISTS quantity DOUBLE PRECISION,
+    ADD IF NOT EXISTS unit VARCHAR(64),
+    ADD IF NOT EXISTS note TEXT;
@@ -0,0 +1,32 @@
+-- Table: list_templates
+
+CREATE TABLE IF NOT EXISTS public.list_templates ();
+
+ALTER TABLE public.list_templates
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    ADD IF NOT EXISTS name VARCHAR(256) NOT NULL;
+
+-- Table: list_template_items
+
+CREATE TABLE IF NOT EXISTS public.list_template_items ();
+
+ALTER TABLE public.list_template_items
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    ADD IF NOT EXISTS list_template_id UUID NOT NULL REFERENCES public.list_templates (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS product_id UUID REFERENCES public.products (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS temporary_name VARCHAR(256),
+    ADD IF NOT EXISTS quantity DOUBLE PRECISION,
+    ADD IF NOT EXISTS unit VARCHAR(64),
+    ADD IF NOT EXISTS note TEXT,
+    ADD IF NOT EXISTS sequence_number INTEGER NOT NULL,
+
+    ADD CONSTRAINT holds_exactly_one_list_template_item_reference CHECK (
+        (product_id IS NOT NULL)::INTEGER +
+        (temporary_name IS NOT NULL)::INTEGER = 1
+    );
@@ -0,0 +1,33 @@
+-- Table: lists
+
+ALTER TABLE public.lists
+    ADD IF NOT EXISTS archived BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Table: trips
+
+CREATE TABLE IF NOT EXISTS public.trips ();
+
+ALTER TABLE public.trips
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    ADD IF NOT EXISTS ts_completed TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS list_id UUID NOT NULL REFERENCES public.lists (id)
+        ON DELETE CASCADE;
+
+-- Table: trip_items
+
+CREATE TABLE IF NOT EXISTS public.trip_items ();
+
+ALTER TABLE public.trip_items
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    ADD IF NOT EXISTS trip_id UUID NOT NULL REFERENCES public.trips (id)
+        ON DELETE CASCADE,
+    -- Names are copied, so history remains readable after a product is removed
+    ADD IF NOT EXISTS product_id UUID REFERENCES public.products (id)
+        ON DELETE SET NULL,
+    ADD IF NOT EXISTS name VARCHAR(256) NOT NULL,
+    ADD IF NOT EXISTS quantity DOUBLE PRECISION,
+    ADD IF NOT EXISTS unit VARCHAR(64);
@@ -0,0 +1,38 @@
+-- Table: list_items
+
+ALTER TABLE public.list_items
+    ADD IF NOT EXISTS ts_checked TIMESTAMP WITH TIME ZONE;
+
+-- Table: product_positions
+
+CREATE TABLE IF NOT EXISTS public.product_positions ();
+
+ALTER TABLE public.product_positions
+    ADD IF NOT EXISTS product_id UUID NOT NULL PRIMARY KEY REFERENCES public.products (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    -- Relative position within a trip, from 0 (first checked) to 1 (last checked)
+    ADD IF NOT EXISTS position DOUBLE PRECISION NOT NULL,
+    ADD IF NOT EXISTS observations INTEGER NOT NULL DEFAULT 1;
+
+-- Function/trigger: record when a list item is checked
+
+CREATE OR REPLACE FUNCTION public.set_list_item_ts_checked()
+RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+    DECLARE
+    BEGIN
+        IF NOT NEW.checked THEN
+            NEW.ts_checked = NULL;
+        ELSIF TG_OP = 'INSERT' OR NOT OLD.checked THEN
+            NEW.ts_checked = NOW();
+        END IF;
+        RETURN NEW;
+    END;
+$$;
+
+CREATE OR REPLACE TRIGGER set_list_item_ts_checked
+BEFORE INSERT OR UPDATE OF checked ON public.list_items
+FOR EACH ROW
+EXECUTE FUNCTION set_list_item_ts_checked();
@@ -0,0 +1,5 @@
+-- Type: page types
+
+ALTER TYPE page_type ADD VALUE IF NOT EXISTS 'note';
+ALTER TYPE page_type ADD VALUE IF NOT EXISTS 'article';
+ALTER TYPE page_type ADD VALUE IF NOT EXISTS 'meal_plan';
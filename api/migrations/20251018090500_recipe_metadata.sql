@@ -0,0 +1,27 @@
+-- Type: recipe difficulties
+
+CREATE TYPE recipe_difficulty AS ENUM (
+    'easy',
+    'medium',
+    'hard'
+);
+
+-- Table: recipe_metadata
+
+CREATE TABLE IF NOT EXISTS public.recipe_metadata ();
+
+ALTER TABLE public.recipe_metadata
+    ADD IF NOT EXISTS page_id UUID NOT NULL PRIMARY KEY REFERENCES public.pages (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    -- Times are in minutes
+    ADD IF NOT EXISTS prep_time INTEGER,
+    ADD IF NOT EXISTS cook_time INTEGER,
+    ADD IF NOT EXISTS total_time INTEGER,
+    ADD IF NOT EXISTS servings DOUBLE PRECISION,
+    ADD IF NOT EXISTS servings_unit VARCHAR(64),
+    ADD IF NOT EXISTS difficulty recipe_difficulty,
+    ADD IF NOT EXISTS cuisine VARCHAR(256),
+    ADD IF NOT EXISTS source_url TEXT,
+    ADD IF NOT EXISTS source_author VARCHAR(256);
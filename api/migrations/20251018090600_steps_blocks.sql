@@ -0,0 +1,79 @@
+-- Table: steps_blocks
+
+CREATE TABLE IF NOT EXISTS public.steps_blocks ();
+
+ALTER TABLE public.steps_blocks
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE;
+
+-- Table: steps
+
+CREATE TABLE IF NOT EXISTS public.steps ();
+
+ALTER TABLE public.steps
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    ADD IF NOT EXISTS steps_block_id UUID NOT NULL REFERENCES public.steps_blocks (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS sequence_number INTEGER NOT NULL,
+    ADD IF NOT EXISTS text TEXT NOT NULL,
+    -- Duration in minutes
+    ADD IF NOT EXISTS duration INTEGER;
+
+-- Table: step_ingredients
+
+CREATE TABLE IF NOT EXISTS public.step_ingredients ();
+
+ALTER TABLE public.step_ingredients
+    ADD IF NOT EXISTS step_id UUID NOT NULL REFERENCES public.steps (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS ingredient_id UUID NOT NULL REFERENCES public.ingredients (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS sequence_number INTEGER NOT NULL,
+
+    ADD PRIMARY KEY (step_id, ingredient_id);
+
+-- Table: blocks
+
+ALTER TABLE public.blocks
+    ADD IF NOT EXISTS steps_block_id UUID REFERENCES public.steps_blocks (id)
+        ON DELETE CASCADE,
+
+    DROP CONSTRAINT IF EXISTS holds_exactly_one_block_reference,
+    ADD CONSTRAINT holds_exactly_one_block_reference CHECK (
+        (ingredient_collection_block_id IS NOT NULL)::INTEGER +
+        (markdown_block_id IS NOT NULL)::INTEGER +
+        (steps_block_id IS NOT NULL)::INTEGER = 1
+    );
+
+-- Function/trigger: delete block references when deleting block
+
+CREATE OR REPLACE FUNCTION public.delete_block_references()
+RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+    DECLARE
+    BEGIN
+        -- Delete ingredient collection block
+        IF OLD.ingredient_collection_block_id IS NOT NULL THEN
+            DELETE FROM public.ingredient_collection_blocks
+            WHERE id = OLD.ingredient_collection_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete markdown block
+        IF OLD.markdown_block_id IS NOT NULL THEN
+            DELETE FROM public.markdown_blocks
+            WHERE id = OLD.markdown_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete steps block
+        IF OLD.steps_block_id IS NOT NULL THEN
+            DELETE FROM public.steps_blocks
+            WHERE id = OLD.steps_block_id;
+            RETURN OLD;
+        END IF;
+    END;
+$$;
@@ -0,0 +1,69 @@
+-- Table: page_reference_blocks
+
+CREATE TABLE IF NOT EXISTS public.page_reference_blocks ();
+
+ALTER TABLE public.page_reference_blocks
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    ADD IF NOT EXISTS page_id UUID NOT NULL REFERENCES public.pages (id)
+        ON DELETE RESTRICT;
+
+-- Table: blocks
+
+ALTER TABLE public.blocks
+    ADD IF NOT EXISTS page_reference_block_id UUID REFERENCES public.page_reference_blocks (id)
+        ON DELETE CASCADE,
+
+    DROP CONSTRAINT IF EXISTS holds_exactly_one_block_reference,
+    ADD CONSTRAINT holds_exactly_one_block_reference CHECK (
+        (ingredient_collection_block_id IS NOT NULL)::INTEGER +
+        (markdown_block_id IS NOT NULL)::INTEGER +
+        (steps_block_id IS NOT NULL)::INTEGER +
+        (image_block_id IS NOT NULL)::INTEGER +
+        (page_reference_block_id IS NOT NULL)::INTEGER = 1
+    );
+
+-- Function/trigger: delete block references when deleting block
+
+CREATE OR REPLACE FUNCTION public.delete_block_references()
+RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+    DECLARE
+    BEGIN
+        -- Delete ingredient collection block
+        IF OLD.ingredient_collection_block_id IS NOT NULL THEN
+            DELETE FROM public.ingredient_collection_blocks
+            WHERE id = OLD.ingredient_collection_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete markdown block
+        IF OLD.markdown_block_id IS NOT NULL THEN
+            DELETE FROM public.markdown_blocks
+            WHERE id = OLD.markdown_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete steps block
+        IF OLD.steps_block_id IS NOT NULL THEN
+            DELETE FROM public.steps_blocks
+            WHERE id = OLD.steps_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete image block
+        IF OLD.image_block_id IS NOT NULL THEN
+            DELETE FROM public.image_blocks
+            WHERE id = OLD.image_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete page reference block
+        IF OLD.page_reference_block_id IS NOT NULL THEN
+            DELETE FROM public.page_reference_blocks
+            WHERE id = OLD.page_reference_block_id;
+            RETURN OLD;
+        END IF;
+    END;
+$$;
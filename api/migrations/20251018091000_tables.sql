@@ -0,0 +1,143 @@
+-- Type: table column alignments
+
+CREATE TYPE table_alignment AS ENUM (
+    'left',
+    'center',
+    'right'
+);
+
+-- Table: tables
+
+CREATE TABLE IF NOT EXISTS public.tables ();
+
+ALTER TABLE public.tables
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE;
+
+-- Table: table_columns
+
+CREATE TABLE IF NOT EXISTS public.table_columns ();
+
+ALTER TABLE public.table_columns
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS table_id UUID NOT NULL REFERENCES public.tables (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS sequence_number INTEGER NOT NULL,
+    ADD IF NOT EXISTS header TEXT NOT NULL,
+    ADD IF NOT EXISTS alignment table_alignment NOT NULL DEFAULT 'left';
+
+-- Table: table_rows
+
+CREATE TABLE IF NOT EXISTS public.table_rows ();
+
+ALTER TABLE public.table_rows
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS table_id UUID NOT NULL REFERENCES public.tables (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS sequence_number INTEGER NOT NULL;
+
+-- Table: table_cells
+
+CREATE TABLE IF NOT EXISTS public.table_cells ();
+
+ALTER TABLE public.table_cells
+    ADD IF NOT EXISTS row_id UUID NOT NULL REFERENCES public.table_rows (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS column_id UUID NOT NULL REFERENCES public.table_columns (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS text TEXT,
+    ADD IF NOT EXISTS number DOUBLE PRECISION,
+
+    ADD PRIMARY KEY (row_id, column_id),
+    -- Empty cells are not stored at all
+    ADD CONSTRAINT holds_exactly_one_value CHECK (
+        (text IS NOT NULL)::INTEGER +
+        (number IS NOT NULL)::INTEGER = 1
+    );
+
+-- Table: table_blocks
+
+CREATE TABLE IF NOT EXISTS public.table_blocks ();
+
+ALTER TABLE public.table_blocks
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS ts_updated TIMESTAMP WITH TIME ZONE,
+    ADD IF NOT EXISTS table_id UUID REFERENCES public.tables (id)
+        ON DELETE CASCADE;
+
+-- Table: blocks
+
+ALTER TABLE public.blocks
+    ADD IF NOT EXISTS table_block_id UUID REFERENCES public.table_blocks (id)
+        ON DELETE CASCADE,
+
+    DROP CONSTRAINT IF EXISTS holds_exactly_one_block_reference,
+    ADD CONSTRAINT holds_exactly_one_block_reference CHECK (
+        (ingredient_collection_block_id IS NOT NULL)::INTEGER +
+        (markdown_block_id IS NOT NULL)::INTEGER +
+        (steps_block_id IS NOT NULL)::INTEGER +
+        (image_block_id IS NOT NULL)::INTEGER +
+        (page_reference_block_id IS NOT NULL)::INTEGER +
+        (checklist_block_id IS NOT NULL)::INTEGER +
+        (table_block_id IS NOT NULL)::INTEGER = 1
+    );
+
+-- Function/trigger: delete block references when deleting block
+
+CREATE OR REPLACE FUNCTION public.delete_block_references()
+RETURNS TRIGGER
+LANGUAGE plpgsql AS $$
+    DECLARE
+    BEGIN
+        -- Delete ingredient collection block
+        IF OLD.ingredient_collection_block_id IS NOT NULL THEN
+            DELETE FROM public.ingredient_collection_blocks
+            WHERE id = OLD.ingredient_collection_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete markdown block
+        IF OLD.markdown_block_id IS NOT NULL THEN
+            DELETE FROM public.markdown_blocks
+            WHERE id = OLD.markdown_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete steps block
+        IF OLD.steps_block_id IS NOT NULL THEN
+            DELETE FROM public.steps_blocks
+            WHERE id = OLD.steps_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete image block
+        IF OLD.image_block_id IS NOT NULL THEN
+            DELETE FROM public.image_blocks
+            WHERE id = OLD.image_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete page reference block
+        IF OLD.page_reference_block_id IS NOT NULL THEN
+            DELETE FROM public.page_reference_blocks
+            WHERE id = OLD.page_reference_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete checklist block
+        IF OLD.checklist_block_id IS NOT NULL THEN
+            DELETE FROM public.checklist_blocks
+            WHERE id = OLD.checklist_block_id;
+            RETURN OLD;
+        END IF;
+
+        -- Delete table block
+        IF OLD.table_block_id IS NOT NULL THEN
+            DELETE FROM public.table_blocks
+            WHERE id = OLD.table_block_id;
+            RETURN OLD;
+        END IF;
+    END;
+$$;
@@ -0,0 +1,15 @@
+-- Table: page_revisions
+
+CREATE TABLE IF NOT EXISTS public.page_revisions ();
+
+ALTER TABLE public.page_revisions
+    ADD IF NOT EXISTS id UUID NOT NULL PRIMARY KEY,
+    ADD IF NOT EXISTS ts_created TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD IF NOT EXISTS page_id UUID NOT NULL REFERENCES public.pages (id)
+        ON DELETE CASCADE,
+    ADD IF NOT EXISTS number INTEGER NOT NULL,
+    -- The page as returned by the API at the time of the revision
+    ADD IF NOT EXISTS snapshot JSONB NOT NULL;
+
+ALTER TABLE public.page_revisions
+    ADD CONSTRAINT unique_page_revision_number UNIQUE (page_id, number);
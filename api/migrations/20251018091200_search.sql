@@ -0,0 +1,22 @@
+-- Full-text search uses the simple configuration, as names and text mix several languages
+
+ALTER TABLE public.pages
+    ADD IF NOT EXISTS search_vector TSVECTOR
+        GENERATED ALWAYS AS (to_tsvector('simple', name)) STORED;
+
+CREATE INDEX IF NOT EXISTS pages_search_vector_index
+    ON public.pages USING GIN (search_vector);
+
+ALTER TABLE public.markdown
+    ADD IF NOT EXISTS search_vector TSVECTOR
+        GENERATED ALWAYS AS (to_tsvector('simple', markdown)) STORED;
+
+CREATE INDEX IF NOT EXISTS markdown_search_vector_index
+    ON public.markdown USING GIN (search_vector);
+
+ALTER TABLE public.products
+    ADD IF NOT EXISTS search_vector TSVECTOR
+        GENERATED ALWAYS AS (to_tsvector('simple', name)) STORED;
+
+CREATE INDEX IF NOT EXISTS products_search_vector_index
+    ON public.products USING GIN (search_vector);
@@ -0,0 +1,60 @@
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- Function: normalize_text
+
+-- unaccent itself is only stable, as its dictionary could change, which keeps it out of indexes
+CREATE OR REPLACE FUNCTION public.normalize_text(value TEXT) RETURNS TEXT AS $$
+    SELECT lower(public.unaccent('public.unaccent'::REGDICTIONARY, value))
+$$ LANGUAGE SQL IMMUTABLE STRICT PARALLEL SAFE;
+
+CREATE INDEX IF NOT EXISTS products_normalized_name_index
+    ON public.products USING GIN (public.normalize_text(name) gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS pages_normalized_name_index
+    ON public.pages USING GIN (public.normalize_text(name) gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS recipe_metadata_normalized_cuisine_index
+    ON public.recipe_metadata USING GIN (public.normalize_text(cuisine) gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS recipe_metadata_normalized_source_author_index
+    ON public.recipe_metadata USING GIN (public.normalize_text(source_author) gin_trgm_ops);
+
+-- Text search configuration: simple_unaccent
+
+CREATE TEXT SEARCH CONFIGURATION public.simple_unaccent (COPY = simple);
+
+ALTER TEXT SEARCH CONFIGURATION public.simple_unaccent
+    ALTER MAPPING FOR hword, hword_part, word WITH public.unaccent, simple;
+
+-- Search vectors are regenerated with accents removed
+
+DROP INDEX IF EXISTS public.pages_search_vector_index;
+DROP INDEX IF EXISTS public.markdown_search_vector_index;
+DROP INDEX IF EXISTS public.products_search_vector_index;
+
+ALTER TABLE public.pages
+    DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE public.pages
+    ADD search_vector TSVECTOR
+        GENERATED ALWAYS AS (to_tsvector('public.simple_unaccent', name)) STORED;
+
+ALTER TABLE public.markdown
+    DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE public.markdown
+    ADD search_vector TSVECTOR
+        GENERATED ALWAYS AS (to_tsvector('public.simple_unaccent', markdown)) STORED;
+
+ALTER TABLE public.products
+    DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE public.products
+    ADD search_vector TSVECTOR
+        GENERATED ALWAYS AS (to_tsvector('public.simple_unaccent', name)) STORED;
+
+CREATE INDEX IF NOT EXISTS pages_search_vector_index
+    ON public.pages USING GIN (search_vector);
+
+CREATE INDEX IF NOT EXISTS markdown_search_vector_index
+    ON public.markdown USING GIN (search_vector);
+
+CREATE INDEX IF NOT EXISTS products_search_vector_index
+    ON public.products USING GIN (search_vector);
@@ -0,0 +1,3 @@
+-- Trigram indexes also serve prefix matches, products and pages are indexed already
+CREATE INDEX IF NOT EXISTS lists_normalized_name_index
+    ON public.lists USING GIN (public.normalize_text(name) gin_trgm_ops);
@@ -2,24 +2,44 @@ use crate::global::AppState;
 use axum::Router;
 use std::sync::Arc;
 
+mod autocomplete;
 mod blocks;
+mod checklists;
+mod images;
 mod ingredient_collections;
+mod list_templates;
 mod lists;
 mod markdown;
 mod pages;
 mod products;
+mod search;
+mod shopping_list;
+mod tables;
 
 pub fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
+        .nest("/autocomplete", autocomplete::create_router(state.clone()))
         .nest("/blocks", blocks::create_router(state.clone()))
+        .nest("/checklists", checklists::create_router(state.clone()))
+        .nest("/images", images::create_router(state.clone()))
         .nest(
             "/ingredient-collections",
             ingredient_collections::create_router(state.clone()),
         )
+        .nest(
+            "/list-templates",
+            list_templates::create_router(state.clone()),
+        )
         .nest("/lists", lists::create_router(state.clone()))
         .nest("/markdown", markdown::create_router(state.clone()))
         .nest("/pages", pages::create_router(state.clone()))
         .nest("/products", products::create_router(state.clone()))
+        .nest("/search", search::create_router(state.clone()))
+        .nest(
+            "/shopping-list",
+            shopping_list::create_router(state.clone()),
+        )
+        .nest("/tables", tables::create_router(state.clone()))
 }
 
 pub async fn handle_options() {}
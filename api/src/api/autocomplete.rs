@@ -0,0 +1,67 @@
+use crate::api::handle_options;
+use crate::db::autocomplete::{AutocompleteDb, AutocompleteParams};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::request::collection::GetResponse;
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/", get(get_collection))
+        .route("/", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("GET, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+// Suggests products, pages and lists whose name starts like or resembles the query
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_collection(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AutocompleteParams>,
+) -> impl IntoResponse {
+    let mut db = state.db().autocomplete();
+
+    let items = match db.get_multiple(query).await {
+        Ok(items) => items,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::Validation(message)) => {
+                tracing::error!("query is invalid: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
+            }
+            _ => {
+                tracing::error!("failed to get suggestions: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        },
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(GetResponse {
+            pagination: None,
+            data: items,
+        }),
+    ))
+}
@@ -75,11 +75,19 @@ pub async fn patch_resource(
         Err(err) => match err.downcast_ref::<DbError>() {
             Some(DbError::NotFound) => {
                 tracing::error!("item could not be found: {:?}", err);
-                return Err(StatusCode::NOT_FOUND);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("operation may not be performed: {:?}", err);
+                return Err(StatusCode::BAD_REQUEST.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("item is invalid: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
             }
             _ => {
                 tracing::error!("failed to update item: {:?}", err);
-                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
             }
         },
     };
@@ -99,11 +107,15 @@ pub async fn delete_resource(
         match err.downcast_ref::<DbError>() {
             Some(DbError::NotFound) => {
                 tracing::error!("item could not be found: {:?}", err);
-                return Err(StatusCode::NOT_FOUND);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("pages would become invalid: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
             }
             _ => {
                 tracing::error!("failed to delete item: {:?}", err);
-                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
             }
         }
     };
@@ -0,0 +1,14 @@
+use crate::global::AppState;
+
+use axum::Router;
+use std::sync::Arc;
+
+mod collection;
+mod items;
+mod resource;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .merge(collection::create_router(state.clone()))
+        .merge(resource::create_router(state))
+}
@@ -0,0 +1,24 @@
+use crate::global::AppState;
+
+use axum::Router;
+use std::sync::Arc;
+use uuid::Uuid;
+
+mod collection;
+mod content;
+mod resource;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .merge(collection::create_router(state.clone()))
+        .merge(content::create_router(state.clone()))
+        .merge(resource::create_router(state))
+}
+
+fn original_key(id: &Uuid) -> String {
+    format!("images/{}/original", id)
+}
+
+fn thumbnail_key(id: &Uuid, width: i32) -> String {
+    format!("images/{}/{}", id, width)
+}
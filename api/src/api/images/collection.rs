@@ -0,0 +1,181 @@
+use crate::api::handle_options;
+use crate::db::images::{ImageCreate, ImageDb};
+use crate::db::Db;
+use crate::global::AppState;
+use crate::storage::Storage;
+use crate::utilities::images::{process, ImageError, MAX_IMAGE_SIZE};
+use crate::utilities::request::collection::GetResponse;
+
+use axum::{
+    extract::{DefaultBodyLimit, Multipart, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+
+use super::{original_key, thumbnail_key};
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new().merge(
+        Router::new()
+            .route("/", get(get_collection))
+            .route("/", post(post_collection))
+            .route("/", options(handle_options))
+            .layer(
+                ServiceBuilder::new()
+                    .layer(SetResponseHeaderLayer::if_not_present(
+                        header::ACCESS_CONTROL_ALLOW_METHODS,
+                        HeaderValue::from_static("GET, POST, OPTIONS"),
+                    ))
+                    .layer(SetResponseHeaderLayer::if_not_present(
+                        header::ACCESS_CONTROL_ALLOW_HEADERS,
+                        HeaderValue::from_static("content-type"),
+                    )),
+            )
+            // Leave some room for the rest of the multipart body
+            .layer(DefaultBodyLimit::max(MAX_IMAGE_SIZE + 64 * 1024))
+            .with_state(state.clone()),
+    )
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_collection(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let mut db = state.db().images();
+
+    let items = match db.get_multiple().await {
+        Ok(items) => items,
+        Err(err) => {
+            tracing::error!("failed to get items: {:?}", err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(GetResponse {
+            pagination: None,
+            data: items,
+        }),
+    ))
+}
+
+// Expects the image as a multipart field named "file"
+#[axum::debug_handler]
+#[instrument(skip(state, multipart))]
+pub async fn post_collection(
+    State(state): State<Arc<AppState>>,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let mut upload = None;
+
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(err) => {
+                tracing::error!("failed to read upload: {:?}", err);
+                return Err(err.status());
+            }
+        };
+
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let name = field.file_name().map(str::to_string);
+        let content_type = field.content_type().map(str::to_string);
+        match field.bytes().await {
+            Ok(bytes) => upload = Some((name, content_type, bytes)),
+            Err(err) => {
+                tracing::error!("failed to read upload: {:?}", err);
+                return Err(err.status());
+            }
+        }
+    }
+
+    let Some((name, content_type, bytes)) = upload else {
+        tracing::error!("upload does not contain a file");
+        return Err(StatusCode::BAD_REQUEST);
+    };
+
+    // Decoding and resizing is too heavy to be done on the runtime
+    let original = bytes.clone();
+    let processed = match tokio::task::spawn_blocking(move || {
+        process(&original, content_type.as_deref())
+    })
+    .await
+    {
+        Ok(Ok(processed)) => processed,
+        Ok(Err(err)) => {
+            tracing::error!("image is invalid: {:?}", err);
+            return Err(match err {
+                ImageError::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+                ImageError::UnsupportedType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                ImageError::Invalid => StatusCode::UNPROCESSABLE_ENTITY,
+            });
+        }
+        Err(err) => {
+            tracing::error!("failed to process image: {:?}", err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let mut db = state.db().images();
+
+    let created = match db
+        .create(ImageCreate {
+            name,
+            content_type: processed.content_type.to_string(),
+            size: bytes.len() as i64,
+            width: processed.width as i32,
+            height: processed.height as i32,
+            thumbnails: processed
+                .thumbnails
+                .iter()
+                .map(|thumbnail| thumbnail.width as i32)
+                .collect(),
+        })
+        .await
+    {
+        Ok(created) => created,
+        Err(err) => {
+            tracing::error!("failed to create item: {:?}", err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    let storage = state.storage();
+
+    let mut objects = vec![(original_key(&created.id), &bytes[..])];
+    for thumbnail in &processed.thumbnails {
+        objects.push((
+            thumbnail_key(&created.id, thumbnail.width as i32),
+            &thumbnail.bytes[..],
+        ));
+    }
+
+    for (index, (key, bytes)) in objects.iter().enumerate() {
+        if let Err(err) = storage.put(key, bytes).await {
+            tracing::error!("failed to store image: {:?}", err);
+
+            for (key, _) in &objects[..index] {
+                if let Err(err) = storage.delete(key).await {
+                    tracing::error!("failed to delete stored image: {:?}", err);
+                }
+            }
+            if let Err(err) = db.delete_by_id(&created.id).await {
+                tracing::error!("failed to delete item: {:?}", err);
+            }
+
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
@@ -0,0 +1,132 @@
+use crate::api::handle_options;
+use crate::db::images::ImageDb;
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::storage::{Storage, StorageError};
+use crate::utilities::images::thumbnail_content_type;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options},
+    Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{original_key, thumbnail_key};
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/content", get(get_content))
+        .route("/:id/content", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("GET, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ContentParams {
+    // Smallest thumbnail at least this wide, or the original if there is none
+    pub width: Option<i32>,
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, headers))]
+pub async fn get_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ContentParams>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let mut db = state.db().images();
+
+    let item = match db.get_by_id(&id).await {
+        Ok(item) => item,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to get item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    let thumbnail = query.width.and_then(|requested| {
+        item.data
+            .thumbnails
+            .iter()
+            .filter(|width| **width >= requested)
+            .min()
+            .copied()
+    });
+    let (key, content_type, etag) = match thumbnail {
+        Some(width) => (
+            thumbnail_key(&id, width),
+            thumbnail_content_type(&item.data.content_type).to_string(),
+            format!("\"{}-{}\"", id, width),
+        ),
+        None => (
+            original_key(&id),
+            item.data.content_type,
+            format!("\"{}-original\"", id),
+        ),
+    };
+
+    // Stored images never change, so anything the client has cached is still valid
+    let cached = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+
+    let headers = [
+        (
+            header::CACHE_CONTROL,
+            "public, max-age=31536000, immutable".to_string(),
+        ),
+        (header::ETAG, etag),
+    ];
+
+    if cached {
+        return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
+    }
+
+    let bytes = match state.storage().get(&key).await {
+        Ok(bytes) => bytes,
+        Err(err) => match err.downcast_ref::<StorageError>() {
+            Some(StorageError::NotFound) => {
+                tracing::error!("stored image could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to get stored image: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((
+        StatusCode::OK,
+        headers,
+        [(header::CONTENT_TYPE, content_type)],
+        bytes,
+    )
+        .into_response())
+}
@@ -0,0 +1,126 @@
+use crate::api::handle_options;
+use crate::db::images::ImageDb;
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::storage::{Storage, StorageError};
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{delete, get, options},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{original_key, thumbnail_key};
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id", get(get_resource))
+        .route("/:id", delete(delete_resource))
+        .route("/:id", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("GET, DELETE, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_resource(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    let mut db = state.db().images();
+
+    let item = match db.get_by_id(&id).await {
+        Ok(item) => item,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to get item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(item)))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn delete_resource(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    let mut db = state.db().images();
+
+    let item = match db.get_by_id(&id).await {
+        Ok(item) => item,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to get item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    // Images still shown by blocks are kept, so those blocks have to be removed first
+    if let Err(err) = db.delete_by_id(&id).await {
+        match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("item is shown by blocks: {:?}", err);
+                return Err(StatusCode::CONFLICT);
+            }
+            _ => {
+                tracing::error!("failed to delete item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    };
+
+    let storage = state.storage();
+
+    let mut keys = vec![original_key(&id)];
+    keys.extend(
+        item.data
+            .thumbnails
+            .iter()
+            .map(|width| thumbnail_key(&id, *width)),
+    );
+
+    // The item is gone either way, so leftover objects are only logged
+    for key in keys {
+        if let Err(err) = storage.delete(&key).await {
+            if err.downcast_ref::<StorageError>() != Some(&StorageError::NotFound) {
+                tracing::error!("failed to delete stored image: {:?}", err);
+            }
+        }
+    }
+
+    Ok(StatusCode::OK)
+}
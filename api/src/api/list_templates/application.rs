@@ -0,0 +1,92 @@
+use crate::api::handle_options;
+use crate::db::list_templates::{
+    ListTemplateApplication, ListTemplateDb, ListTemplateInstantiation,
+};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::request::collection::PostResponse;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/apply", post(post_application))
+        .route("/:id/apply", options(handle_options))
+        .route("/:id/instantiate", post(post_instantiation))
+        .route("/:id/instantiate", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("POST, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_application(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ListTemplateApplication>,
+) -> impl IntoResponse {
+    let mut db = state.db().list_templates();
+
+    let created = match db.apply(&id, payload).await {
+        Ok(created) => created,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to apply template: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(PostResponse { data: created })))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_instantiation(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ListTemplateInstantiation>,
+) -> impl IntoResponse {
+    let mut db = state.db().list_templates();
+
+    let created = match db.instantiate(&id, payload).await {
+        Ok(created) => created,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to instantiate template: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
@@ -4,11 +4,20 @@ use axum::Router;
 use std::sync::Arc;
 
 mod collection;
+mod export;
 mod items;
+mod operations;
+mod quick_add;
 mod resource;
+mod suggestions;
+mod trips;
 
 pub fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
         .merge(collection::create_router(state.clone()))
+        .merge(export::create_router(state.clone()))
+        .merge(operations::create_router(state.clone()))
+        .merge(quick_add::create_router(state.clone()))
+        .merge(suggestions::create_router(state.clone()))
         .merge(resource::create_router(state))
 }
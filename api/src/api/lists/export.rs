@@ -0,0 +1,150 @@
+use crate::api::handle_options;
+use crate::db::list_items::{ListItemKindTemplate, ListItemReference};
+use crate::db::lists::{List, ListDb, ListParams};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::export::{to_html, to_markdown, to_text, ExportItem};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options},
+    Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/export", get(get_export))
+        .route("/:id/export", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("GET, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[derive(Default, Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Text,
+    Markdown,
+    Html,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_export(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ExportParams>,
+) -> impl IntoResponse {
+    let mut db = state.db().lists();
+
+    let list = match db.get_by_id(&id, ListParams::default()).await {
+        Ok(list) => list,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to get item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    let name = &list.data.name;
+    let groups = group(&list);
+    let (content_type, body) = match query.format {
+        ExportFormat::Text => ("text/plain; charset=utf-8", to_text(name, &groups)),
+        ExportFormat::Markdown => ("text/markdown; charset=utf-8", to_markdown(name, &groups)),
+        ExportFormat::Html => ("text/html; charset=utf-8", to_html(name, &groups)),
+    };
+
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, content_type)], body))
+}
+
+// Items grouped by kind, in the order they are listed in the export
+fn group(list: &List) -> Vec<(&'static str, Vec<ExportItem<'_>>)> {
+    let mut products = Vec::new();
+    let mut ingredients = Vec::new();
+    let mut temporaries = Vec::new();
+
+    for item in list.data.item_refs.items.iter().flatten() {
+        let (group, name) = match item_name(item) {
+            Some(ListItemGroup::Product(name)) => (&mut products, name),
+            Some(ListItemGroup::Ingredient(name)) => (&mut ingredients, name),
+            Some(ListItemGroup::Temporary(name)) => (&mut temporaries, name),
+            None => continue,
+        };
+
+        let data = item.data.as_ref();
+        group.push(ExportItem {
+            name,
+            checked: data.and_then(|data| data.checked).unwrap_or_default(),
+            quantity: data.and_then(|data| data.quantity),
+            unit: data.and_then(|data| data.unit.as_deref()),
+            note: data.and_then(|data| data.note.as_deref()),
+        });
+    }
+
+    [
+        ("Products", products),
+        ("Ingredients", ingredients),
+        ("Other", temporaries),
+    ]
+    .into_iter()
+    .filter(|(_, items)| !items.is_empty())
+    .collect()
+}
+
+enum ListItemGroup<'a> {
+    Product(&'a str),
+    Ingredient(&'a str),
+    Temporary(&'a str),
+}
+
+fn item_name(item: &ListItemReference) -> Option<ListItemGroup<'_>> {
+    match item.data.as_ref()?.kind.as_ref()? {
+        ListItemKindTemplate::Product { product, .. } => Some(ListItemGroup::Product(
+            product.as_ref()?.data.as_ref()?.name.as_deref()?,
+        )),
+        ListItemKindTemplate::Ingredient { ingredient, .. } => Some(ListItemGroup::Ingredient(
+            ingredient
+                .as_ref()?
+                .data
+                .as_ref()?
+                .product
+                .as_ref()?
+                .data
+                .as_ref()?
+                .name
+                .as_deref()?,
+        )),
+        ListItemKindTemplate::Temporary { temporary, .. } => Some(ListItemGroup::Temporary(
+            temporary.as_ref()?.data.as_ref()?.name.as_deref()?,
+        )),
+    }
+}
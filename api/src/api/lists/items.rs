@@ -3,11 +3,19 @@ use crate::global::AppState;
 use axum::Router;
 use std::sync::Arc;
 
+mod batch;
 mod collection;
+mod conversion;
+mod resolution;
 mod resource;
+mod suggestions;
 
 pub fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
+        .merge(batch::create_router(state.clone()))
         .merge(collection::create_router(state.clone()))
+        .merge(conversion::create_router(state.clone()))
+        .merge(resolution::create_router(state.clone()))
+        .merge(suggestions::create_router(state.clone()))
         .merge(resource::create_router(state))
 }
@@ -0,0 +1,72 @@
+use crate::api::handle_options;
+use crate::db::list_items::{ListItemBatchOperation, ListItemDb};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::request::collection::{GetResponse, PostRequest};
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/batch", post(post_batch))
+        .route("/batch", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("POST, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_batch(
+    State(state): State<Arc<AppState>>,
+    Path(list_id): Path<Uuid>,
+    Json(payload): Json<PostRequest<ListItemBatchOperation>>,
+) -> impl IntoResponse {
+    let mut db = state.db().list_items();
+
+    let items = match db.batch(&list_id, payload.data).await {
+        Ok(items) => items,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("operation may not be performed: {:?}", err);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            _ => {
+                tracing::error!("failed to perform batch operations: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(GetResponse {
+            pagination: None,
+            data: items,
+        }),
+    ))
+}
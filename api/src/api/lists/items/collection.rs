@@ -1,7 +1,10 @@
 use crate::db::list_items::{ListItemCreate, ListItemDb};
 use crate::global::AppState;
 use crate::utilities::request::collection::{GetResponse, PostRequest, PostResponse};
-use crate::{api::handle_options, db::Db};
+use crate::{
+    api::handle_options,
+    db::{Db, DbError},
+};
 
 use axum::extract::Path;
 use axum::{
@@ -74,10 +77,16 @@ pub async fn post_collection(
 
     let created = match db.create_multiple(&list_id, payload.data).await {
         Ok(created) => created,
-        Err(err) => {
-            tracing::error!("failed to create items: {:?}", err);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("list could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to create items: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
     };
 
     Ok((StatusCode::CREATED, Json(PostResponse { data: created })))
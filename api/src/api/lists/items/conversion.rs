@@ -0,0 +1,65 @@
+use crate::api::handle_options;
+use crate::db::list_items::{ListItemConversion, ListItemDb};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/convert", post(post_conversion))
+        .route("/:id/convert", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("POST, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_conversion(
+    State(state): State<Arc<AppState>>,
+    Path((list_id, id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ListItemConversion>,
+) -> impl IntoResponse {
+    let mut db = state.db().list_items();
+
+    let converted = match db.convert_to_product(&list_id, &id, payload).await {
+        Ok(converted) => converted,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("item cannot be converted: {:?}", err);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            _ => {
+                tracing::error!("failed to convert item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(converted)))
+}
@@ -0,0 +1,62 @@
+use crate::api::handle_options;
+use crate::db::list_items::{ListItemDb, ListItemResolution};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::request::collection::PostResponse;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/resolve", post(post_resolution))
+        .route("/resolve", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("POST, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_resolution(
+    State(state): State<Arc<AppState>>,
+    Path(list_id): Path<Uuid>,
+    Json(payload): Json<ListItemResolution>,
+) -> impl IntoResponse {
+    let mut db = state.db().list_items();
+
+    let resolved = match db.resolve_temporaries(&list_id, payload).await {
+        Ok(resolved) => resolved,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("list could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to resolve items: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(PostResponse { data: resolved })))
+}
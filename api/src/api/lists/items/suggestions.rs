@@ -0,0 +1,71 @@
+use crate::api::handle_options;
+use crate::db::list_items::ListItemDb;
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::request::collection::GetResponse;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/suggestions", get(get_suggestions))
+        .route("/:id/suggestions", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("GET, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_suggestions(
+    State(state): State<Arc<AppState>>,
+    Path((list_id, id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let mut db = state.db().list_items();
+
+    let suggestions = match db.get_suggestions(&list_id, &id).await {
+        Ok(suggestions) => suggestions,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("item is not a temporary item: {:?}", err);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            _ => {
+                tracing::error!("failed to get suggestions: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(GetResponse {
+            pagination: None,
+            data: suggestions,
+        }),
+    ))
+}
@@ -0,0 +1,93 @@
+use crate::api::handle_options;
+use crate::db::lists::{ListDb, ListDuplication, ListMerge};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/duplicate", post(post_duplication))
+        .route("/:id/duplicate", options(handle_options))
+        .route("/:id/merge", post(post_merge))
+        .route("/:id/merge", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("POST, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_duplication(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ListDuplication>,
+) -> impl IntoResponse {
+    let mut db = state.db().lists();
+
+    let created = match db.duplicate(&id, payload).await {
+        Ok(created) => created,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to duplicate list: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_merge(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ListMerge>,
+) -> impl IntoResponse {
+    let mut db = state.db().lists();
+
+    let merged = match db.merge(&id, payload).await {
+        Ok(merged) => merged,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("list cannot be merged into itself: {:?}", err);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            _ => {
+                tracing::error!("failed to merge lists: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(merged)))
+}
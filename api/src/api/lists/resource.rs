@@ -1,10 +1,10 @@
 use crate::api::handle_options;
-use crate::db::lists::{ListDb, ListUpdate};
+use crate::db::lists::{ListDb, ListParams, ListUpdate};
 use crate::db::{Db, DbError};
 use crate::global::AppState;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, HeaderValue, StatusCode},
     response::IntoResponse,
     routing::{delete, get, options, patch},
@@ -16,7 +16,7 @@ use tower_http::set_header::SetResponseHeaderLayer;
 use tracing::instrument;
 use uuid::Uuid;
 
-use super::items;
+use super::{items, trips};
 
 pub fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
@@ -37,6 +37,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         )
         .with_state(state.clone())
         .nest("/:id/items", items::create_router(state.clone()))
+        .nest("/:id/trips", trips::create_router(state.clone()))
 }
 
 #[axum::debug_handler]
@@ -44,10 +45,11 @@ pub fn create_router(state: Arc<AppState>) -> Router {
 pub async fn get_resource(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
+    Query(query): Query<ListParams>,
 ) -> impl IntoResponse {
     let mut db = state.db().lists();
 
-    let item = match db.get_by_id(&id).await {
+    let item = match db.get_by_id(&id, query).await {
         Ok(block) => block,
         Err(err) => match err.downcast_ref::<DbError>() {
             Some(DbError::NotFound) => {
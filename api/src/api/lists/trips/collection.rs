@@ -0,0 +1,94 @@
+use crate::db::trips::TripDb;
+use crate::db::DbError;
+use crate::global::AppState;
+use crate::utilities::request::collection::GetResponse;
+use crate::{api::handle_options, db::Db};
+
+use axum::extract::Path;
+use axum::{
+    extract::State,
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new().merge(
+        Router::new()
+            .route("/", get(get_collection))
+            .route("/", post(post_collection))
+            .route("/", options(handle_options))
+            .layer(
+                ServiceBuilder::new()
+                    .layer(SetResponseHeaderLayer::if_not_present(
+                        header::ACCESS_CONTROL_ALLOW_METHODS,
+                        HeaderValue::from_static("GET, POST, OPTIONS"),
+                    ))
+                    .layer(SetResponseHeaderLayer::if_not_present(
+                        header::ACCESS_CONTROL_ALLOW_HEADERS,
+                        HeaderValue::from_static("content-type"),
+                    )),
+            )
+            .with_state(state.clone()),
+    )
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_collection(
+    State(state): State<Arc<AppState>>,
+    Path(list_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let mut db = state.db().trips();
+
+    let items = match db.get_multiple(&list_id).await {
+        Ok(items) => items,
+        Err(err) => {
+            tracing::error!("failed to get items: {:?}", err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(GetResponse {
+            pagination: None,
+            data: items,
+        }),
+    ))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn post_collection(
+    State(state): State<Arc<AppState>>,
+    Path(list_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let mut db = state.db().trips();
+
+    let completed = match db.complete(&list_id).await {
+        Ok(completed) => completed,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("list could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("list has no checked items: {:?}", err);
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            _ => {
+                tracing::error!("failed to complete trip: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(completed)))
+}
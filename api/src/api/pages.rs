@@ -3,11 +3,17 @@ use crate::global::AppState;
 use axum::Router;
 use std::sync::Arc;
 
+mod blocks;
 mod collection;
+mod operations;
 mod resource;
+mod revisions;
 
 pub fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
+        .merge(blocks::create_router(state.clone()))
         .merge(collection::create_router(state.clone()))
+        .merge(operations::create_router(state.clone()))
+        .merge(revisions::create_router(state.clone()))
         .merge(resource::create_router(state))
 }
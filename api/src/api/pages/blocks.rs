@@ -0,0 +1,128 @@
+use crate::api::handle_options;
+use crate::db::pages::{PageBlockInsert, PageBlockMove, PageDb};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{delete, options, patch, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/blocks", post(post_block))
+        .route("/:id/blocks", options(handle_options))
+        .route("/:id/blocks/:link_id", patch(patch_block))
+        .route("/:id/blocks/:link_id", delete(delete_block))
+        .route("/:id/blocks/:link_id", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("POST, PATCH, DELETE, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+// Attaches an existing block, or one defined inline, and returns it as it appears on the page
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_block(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<PageBlockInsert>,
+) -> impl IntoResponse {
+    let mut db = state.db().pages();
+
+    let created = match db.insert_block(&id, payload).await {
+        Ok(created) => created,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("block could not be inserted: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
+            }
+            _ => {
+                tracing::error!("failed to insert block: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        },
+    };
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn patch_block(
+    State(state): State<Arc<AppState>>,
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<PageBlockMove>,
+) -> impl IntoResponse {
+    let mut db = state.db().pages();
+
+    let updated = match db.move_block(&id, &link_id, payload).await {
+        Ok(updated) => updated,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("block could not be moved: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
+            }
+            _ => {
+                tracing::error!("failed to move block: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(updated)))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn delete_block(
+    State(state): State<Arc<AppState>>,
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let mut db = state.db().pages();
+
+    let updated = match db.remove_block(&id, &link_id).await {
+        Ok(updated) => updated,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("block could not be removed: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
+            }
+            _ => {
+                tracing::error!("failed to remove block: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(updated)))
+}
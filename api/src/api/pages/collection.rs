@@ -1,4 +1,5 @@
 use crate::db::pages::{PageCreate, PageDb, SearchParams};
+use crate::db::DbError;
 use crate::global::AppState;
 use crate::utilities::request::collection::{GetResponse, PostRequest, PostResponse};
 use crate::{api::handle_options, db::Db};
@@ -72,10 +73,20 @@ pub async fn post_collection(
 
     let created = match db.create_multiple(payload.data).await {
         Ok(created) => created,
-        Err(err) => {
-            tracing::error!("failed to create items: {:?}", err);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("block could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("items are invalid: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
+            }
+            _ => {
+                tracing::error!("failed to create items: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        },
     };
 
     Ok((StatusCode::CREATED, Json(PostResponse { data: created })))
@@ -0,0 +1,69 @@
+use crate::api::handle_options;
+use crate::db::pages::{PageAddToList, PageDb};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::request::collection::GetResponse;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/add-to-list", post(post_add_to_list))
+        .route("/:id/add-to-list", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("POST, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+// Adds the ingredients of the page and every page it references to a list
+#[axum::debug_handler]
+#[instrument(skip(state, payload))]
+pub async fn post_add_to_list(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<PageAddToList>,
+) -> impl IntoResponse {
+    let mut db = state.db().pages();
+
+    let items = match db.add_to_list(&id, payload).await {
+        Ok(items) => items,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to add page to list: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        Json(GetResponse {
+            pagination: None,
+            data: items,
+        }),
+    ))
+}
@@ -1,10 +1,10 @@
 use crate::api::handle_options;
-use crate::db::pages::{PageDb, PageUpdate};
+use crate::db::pages::{PageDb, PageParams, PageUpdate};
 use crate::db::{Db, DbError};
 use crate::global::AppState;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, HeaderValue, StatusCode},
     response::IntoResponse,
     routing::{delete, get, options, patch},
@@ -41,10 +41,11 @@ pub fn create_router(state: Arc<AppState>) -> Router {
 pub async fn get_resource(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
+    Query(query): Query<PageParams>,
 ) -> impl IntoResponse {
     let mut db = state.db().pages();
 
-    let item = match db.get_by_id(&id).await {
+    let item = match db.get_by_id(&id, query).await {
         Ok(block) => block,
         Err(err) => match err.downcast_ref::<DbError>() {
             Some(DbError::NotFound) => {
@@ -75,11 +76,15 @@ pub async fn patch_resource(
         Err(err) => match err.downcast_ref::<DbError>() {
             Some(DbError::NotFound) => {
                 tracing::error!("item could not be found: {:?}", err);
-                return Err(StatusCode::NOT_FOUND);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("item is invalid: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
             }
             _ => {
                 tracing::error!("failed to update item: {:?}", err);
-                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
             }
         },
     };
@@ -101,6 +106,10 @@ pub async fn delete_resource(
                 tracing::error!("item could not be found: {:?}", err);
                 return Err(StatusCode::NOT_FOUND);
             }
+            Some(DbError::InvalidOperation) => {
+                tracing::error!("item is referenced by other pages: {:?}", err);
+                return Err(StatusCode::CONFLICT);
+            }
             _ => {
                 tracing::error!("failed to delete item: {:?}", err);
                 return Err(StatusCode::INTERNAL_SERVER_ERROR);
@@ -0,0 +1,152 @@
+use crate::api::handle_options;
+use crate::db::page_revisions::{PageRevisionDb, PageRevisionDiffParams};
+use crate::db::{Db, DbError};
+use crate::global::AppState;
+use crate::utilities::request::collection::GetResponse;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options, post},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+use uuid::Uuid;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/:id/revisions", get(get_collection))
+        .route("/:id/revisions", options(handle_options))
+        .route("/:id/revisions/:revision_id", get(get_resource))
+        .route("/:id/revisions/:revision_id", options(handle_options))
+        .route("/:id/revisions/:revision_id/diff", get(get_diff))
+        .route("/:id/revisions/:revision_id/diff", options(handle_options))
+        .route("/:id/revisions/:revision_id/restore", post(post_restore))
+        .route(
+            "/:id/revisions/:revision_id/restore",
+            options(handle_options),
+        )
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("GET, POST, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+// Newest revisions come first and are listed without their snapshot
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_collection(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    let mut db = state.db().page_revisions();
+
+    let items = match db.get_multiple(&id).await {
+        Ok(items) => items,
+        Err(err) => {
+            tracing::error!("failed to get items: {:?}", err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(GetResponse {
+            pagination: None,
+            data: items,
+        }),
+    ))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_resource(
+    State(state): State<Arc<AppState>>,
+    Path((id, revision_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let mut db = state.db().page_revisions();
+
+    let item = match db.get_by_id(&id, &revision_id).await {
+        Ok(item) => item,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to get item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(item)))
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_diff(
+    State(state): State<Arc<AppState>>,
+    Path((id, revision_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<PageRevisionDiffParams>,
+) -> impl IntoResponse {
+    let mut db = state.db().page_revisions();
+
+    let diff = match db.diff(&id, &revision_id, query).await {
+        Ok(diff) => diff,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND);
+            }
+            _ => {
+                tracing::error!("failed to compare items: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(diff)))
+}
+
+// Restoring is recorded as a new revision, the restored revision itself is left untouched
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn post_restore(
+    State(state): State<Arc<AppState>>,
+    Path((id, revision_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    let mut db = state.db().page_revisions();
+
+    let restored = match db.restore(&id, &revision_id).await {
+        Ok(restored) => restored,
+        Err(err) => match err.downcast_ref::<DbError>() {
+            Some(DbError::NotFound) => {
+                tracing::error!("item could not be found: {:?}", err);
+                return Err(StatusCode::NOT_FOUND.into_response());
+            }
+            Some(DbError::Validation(message)) => {
+                tracing::error!("item could not be restored: {:?}", err);
+                return Err((StatusCode::UNPROCESSABLE_ENTITY, message.clone()).into_response());
+            }
+            _ => {
+                tracing::error!("failed to restore item: {:?}", err);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        },
+    };
+
+    Ok((StatusCode::OK, Json(restored)))
+}
@@ -5,9 +5,11 @@ use std::sync::Arc;
 
 mod collection;
 mod resource;
+mod statistics;
 
 pub fn create_router(state: Arc<AppState>) -> Router {
     Router::new()
         .merge(collection::create_router(state.clone()))
+        .merge(statistics::create_router(state.clone()))
         .merge(resource::create_router(state))
 }
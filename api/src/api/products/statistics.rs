@@ -0,0 +1,60 @@
+use crate::api::handle_options;
+use crate::db::trips::{StatisticsParams, TripDb};
+use crate::db::Db;
+use crate::global::AppState;
+use crate::utilities::request::collection::GetResponse;
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    routing::{get, options},
+    Json, Router,
+};
+use std::sync::Arc;
+use tower::ServiceBuilder;
+use tower_http::set_header::SetResponseHeaderLayer;
+use tracing::instrument;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/statistics", get(get_statistics))
+        .route("/statistics", options(handle_options))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_METHODS,
+                    HeaderValue::from_static("GET, OPTIONS"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    HeaderValue::from_static("content-type"),
+                )),
+        )
+        .with_state(state.clone())
+}
+
+#[axum::debug_handler]
+#[instrument(skip(state))]
+pub async fn get_statistics(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<StatisticsParams>,
+) -> impl IntoResponse {
+    let mut db = state.db().trips();
+
+    let items = match db.get_statistics(query).await {
+        Ok(items) => items,
+        Err(err) => {
+            tracing::error!("failed to get statistics: {:?}", err);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(GetResponse {
+            pagination: None,
+            data: items,
+        }),
+    ))
+}
@@ -0,0 +1,17 @@
+use crate::global::AppState;
+
+use axum::Router;
+use std::sync::Arc;
+
+mod collection;
+mod columns;
+mod resource;
+mod rows;
+
+pub fn create_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .merge(collection::create_router(state.clone()))
+        .merge(columns::create_router(state.clone()))
+        .merge(rows::create_router(state.clone()))
+        .merge(resource::create_router(state))
+}
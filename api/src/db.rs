@@ -2,34 +2,64 @@ use std::error::Error;
 use std::fmt::{Debug, Display};
 
 use anyhow::Result;
+use autocomplete::{AutocompleteDb, AutocompleteDbPostgres};
 use blocks::{BlockDb, BlockDbPostgres};
+use checklist_items::{ChecklistItemDb, ChecklistItemDbPostgres};
+use checklists::{ChecklistDb, ChecklistDbPostgres};
+use images::{ImageDb, ImageDbPostgres};
 use ingredient_collections::{IngredientCollectionDb, IngredientCollectionDbPostgres};
 use ingredients::{IngredientDb, IngredientDbPostgres};
 use list_items::{ListItemDb, ListItemDbPostgres};
+use list_templates::{ListTemplateDb, ListTemplateDbPostgres};
 use lists::{ListDb, ListDbPostgres};
 use markdown::{MarkdownDb, MarkdownDbPostgres};
+use page_revisions::{PageRevisionDb, PageRevisionDbPostgres};
 use pages::{PageDb, PageDbPostgres};
 use products::{ProductDb, ProductDbPostgres};
+use search::{SearchDb, SearchDbPostgres};
+use shopping_list::{ShoppingListDb, ShoppingListDbPostgres};
 use sqlx::PgPool;
+use tables::{TableDb, TableDbPostgres};
+use trips::{TripDb, TripDbPostgres};
 
+pub mod autocomplete;
 pub mod blocks;
+pub mod checklist_items;
+pub mod checklists;
+pub mod images;
 pub mod ingredient_collections;
 pub mod ingredients;
 pub mod list_items;
+pub mod list_templates;
 pub mod lists;
 pub mod markdown;
+pub mod page_revisions;
 pub mod pages;
 pub mod products;
+pub mod search;
+pub mod shopping_list;
+pub mod tables;
+pub mod trips;
 
 pub trait Db {
+    fn autocomplete(&self) -> impl AutocompleteDb;
     fn blocks(&self) -> impl BlockDb;
+    fn checklist_items(&self) -> impl ChecklistItemDb;
+    fn checklists(&self) -> impl ChecklistDb;
+    fn images(&self) -> impl ImageDb;
     fn ingredient_collections(&self) -> impl IngredientCollectionDb;
     fn ingredients(&self) -> impl IngredientDb;
     fn list_items(&self) -> impl ListItemDb;
+    fn list_templates(&self) -> impl ListTemplateDb;
     fn lists(&self) -> impl ListDb;
     fn markdown(&self) -> impl MarkdownDb;
+    fn page_revisions(&self) -> impl PageRevisionDb;
     fn pages(&self) -> impl PageDb;
     fn products(&self) -> impl ProductDb;
+    fn search(&self) -> impl SearchDb;
+    fn shopping_list(&self) -> impl ShoppingListDb;
+    fn tables(&self) -> impl TableDb;
+    fn trips(&self) -> impl TripDb;
     async fn migrate(&self) -> Result<()>;
 }
 
@@ -44,10 +74,26 @@ impl DbPostgres {
 }
 
 impl Db for DbPostgres {
+    fn autocomplete(&self) -> impl AutocompleteDb {
+        AutocompleteDbPostgres::new(&self.sqlx)
+    }
+
     fn blocks(&self) -> impl BlockDb {
         BlockDbPostgres::new(&self.sqlx)
     }
 
+    fn checklist_items(&self) -> impl ChecklistItemDb {
+        ChecklistItemDbPostgres::new(&self.sqlx)
+    }
+
+    fn checklists(&self) -> impl ChecklistDb {
+        ChecklistDbPostgres::new(&self.sqlx)
+    }
+
+    fn images(&self) -> impl ImageDb {
+        ImageDbPostgres::new(&self.sqlx)
+    }
+
     fn ingredient_collections(&self) -> impl IngredientCollectionDb {
         IngredientCollectionDbPostgres::new(&self.sqlx)
     }
@@ -60,6 +106,10 @@ impl Db for DbPostgres {
         ListItemDbPostgres::new(&self.sqlx)
     }
 
+    fn list_templates(&self) -> impl ListTemplateDb {
+        ListTemplateDbPostgres::new(&self.sqlx)
+    }
+
     fn lists(&self) -> impl ListDb {
         ListDbPostgres::new(&self.sqlx)
     }
@@ -68,6 +118,10 @@ impl Db for DbPostgres {
         MarkdownDbPostgres::new(&self.sqlx)
     }
 
+    fn page_revisions(&self) -> impl PageRevisionDb {
+        PageRevisionDbPostgres::new(&self.sqlx)
+    }
+
     fn pages(&self) -> impl PageDb {
         PageDbPostgres::new(&self.sqlx)
     }
@@ -76,6 +130,22 @@ impl Db for DbPostgres {
         ProductDbPostgres::new(&self.sqlx)
     }
 
+    fn search(&self) -> impl SearchDb {
+        SearchDbPostgres::new(&self.sqlx)
+    }
+
+    fn shopping_list(&self) -> impl ShoppingListDb {
+        ShoppingListDbPostgres::new(&self.sqlx)
+    }
+
+    fn tables(&self) -> impl TableDb {
+        TableDbPostgres::new(&self.sqlx)
+    }
+
+    fn trips(&self) -> impl TripDb {
+        TripDbPostgres::new(&self.sqlx)
+    }
+
     async fn migrate(&self) -> Result<()> {
         Ok(sqlx::migrate!().run(&self.sqlx).await?)
     }
@@ -85,6 +155,7 @@ impl Db for DbPostgres {
 pub enum DbError {
     NotFound,
     InvalidOperation,
+    Validation(String),
 }
 
 impl Display for DbError {
@@ -92,6 +163,7 @@ impl Display for DbError {
         match self {
             DbError::NotFound => write!(f, "resource could not be found"),
             DbError::InvalidOperation => write!(f, "operation may not be performed"),
+            DbError::Validation(message) => write!(f, "validation failed: {}", message),
         }
     }
 }
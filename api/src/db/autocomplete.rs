@@ -0,0 +1,194 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::utilities::search::escape_like;
+
+use super::DbError;
+
+#[trait_variant::make(Send)]
+pub trait AutocompleteDb {
+    async fn get_multiple(&mut self, params: AutocompleteParams) -> Result<Vec<Suggestion>>;
+}
+
+// Number of suggestions when no limit is given
+const DEFAULT_TAKE: i64 = 10;
+
+// Larger limits are capped to this number of suggestions
+const MAX_TAKE: i64 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionType {
+    Product,
+    Page,
+    List,
+}
+
+impl SuggestionType {
+    const ALL: [SuggestionType; 3] = [
+        SuggestionType::Product,
+        SuggestionType::Page,
+        SuggestionType::List,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SuggestionType::Product => "product",
+            SuggestionType::Page => "page",
+            SuggestionType::List => "list",
+        }
+    }
+}
+
+// Prefix matches rank above names that are merely similar
+#[derive(Debug, Serialize)]
+pub struct Suggestion {
+    pub r#type: SuggestionType,
+    pub id: Uuid,
+    pub name: String,
+    pub rank: f32,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct AutocompleteParams {
+    pub q: String,
+    // Comma separated, all types are suggested when absent
+    pub types: Option<String>,
+    pub take: Option<i64>,
+}
+
+impl AutocompleteParams {
+    fn types(&self) -> Result<Vec<SuggestionType>> {
+        let Some(types) = &self.types else {
+            return Ok(SuggestionType::ALL.to_vec());
+        };
+
+        let mut parsed = Vec::new();
+        for name in types
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let Some(r#type) = SuggestionType::ALL
+                .into_iter()
+                .find(|r#type| r#type.as_str() == name)
+            else {
+                return Err(
+                    DbError::Validation(format!("unknown suggestion type '{}'", name)).into(),
+                );
+            };
+            parsed.push(r#type);
+        }
+
+        Ok(parsed)
+    }
+
+    fn take(&self) -> Result<i64> {
+        match self.take {
+            Some(take) if take < 0 => {
+                Err(DbError::Validation("take may not be negative".to_string()).into())
+            }
+            Some(take) => Ok(take.min(MAX_TAKE)),
+            None => Ok(DEFAULT_TAKE),
+        }
+    }
+}
+
+pub struct AutocompleteDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> AutocompleteDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl AutocompleteDb for AutocompleteDbPostgres<'_> {
+    async fn get_multiple(&mut self, params: AutocompleteParams) -> Result<Vec<Suggestion>> {
+        let types = params.types()?;
+        let take = params.take()?;
+        if params.q.trim().is_empty() || types.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.pool.acquire().await?;
+
+        // Both the prefix and the similarity condition are served by the trigram indexes on the
+        // normalized names
+        let rows = sqlx::query(
+            "
+            WITH search AS (
+                SELECT
+                    normalize_text($1) AS term,
+                    normalize_text($2) || '%' AS prefix
+            ),
+            suggestions (type, id, name, normalized_name) AS (
+                    SELECT 'product', products.id, products.name, normalize_text(products.name)
+                    FROM public.products, search
+                    WHERE
+                        'product' = ANY($3) AND
+                        (
+                            normalize_text(products.name) LIKE search.prefix OR
+                            normalize_text(products.name) % search.term
+                        )
+                UNION ALL
+                    SELECT 'page', pages.id, pages.name, normalize_text(pages.name)
+                    FROM public.pages, search
+                    WHERE
+                        'page' = ANY($3) AND
+                        (
+                            normalize_text(pages.name) LIKE search.prefix OR
+                            normalize_text(pages.name) % search.term
+                        )
+                UNION ALL
+                    SELECT 'list', lists.id, lists.name, normalize_text(lists.name)
+                    FROM public.lists, search
+                    WHERE
+                        'list' = ANY($3) AND
+                        NOT lists.archived AND
+                        (
+                            normalize_text(lists.name) LIKE search.prefix OR
+                            normalize_text(lists.name) % search.term
+                        )
+            )
+            SELECT
+                suggestions.type,
+                suggestions.id,
+                suggestions.name,
+                (
+                    (suggestions.normalized_name LIKE search.prefix)::INT +
+                    similarity(suggestions.normalized_name, search.term)
+                )::REAL AS rank
+            FROM suggestions, search
+            ORDER BY
+                rank DESC,
+                LENGTH(suggestions.name),
+                suggestions.name
+            LIMIT $4
+            ",
+        )
+        .bind(&params.q)
+        .bind(escape_like(&params.q))
+        .bind(types.iter().map(SuggestionType::as_str).collect::<Vec<_>>())
+        .bind(take)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| Suggestion {
+                r#type: match row.get::<&str, _>("type") {
+                    "product" => SuggestionType::Product,
+                    "page" => SuggestionType::Page,
+                    _ => SuggestionType::List,
+                },
+                id: row.get("id"),
+                name: row.get("name"),
+                rank: row.get("rank"),
+            })
+            .collect())
+    }
+}
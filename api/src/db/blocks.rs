@@ -1,8 +1,9 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{TryFutureExt, TryStreamExt};
+use pulldown_cmark::Options;
 use serde::{Deserialize, Serialize};
-use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgConnection, PgPool, PgTransaction, Row};
 use uuid::Uuid;
 
 use crate::utilities::{
@@ -11,8 +12,14 @@ use crate::utilities::{
 };
 
 use super::{
+    checklists::ChecklistReference,
+    images::{ImageDataTemplate, ImageReference},
     ingredient_collections::{IngredientCollectionDataTemplate, IngredientCollectionReference},
+    ingredients::IngredientReference,
     markdown::{MarkdownDataTemplate, MarkdownReference},
+    page_revisions::PageRevisionDbPostgres,
+    pages::{PageDataTemplate, PageDbPostgres, PageReference},
+    tables::TableReference,
     DbError,
 };
 
@@ -62,6 +69,66 @@ pub enum BlockKindTemplate<M: Modifier> {
         #[serde(flatten)]
         markdown: M::Data<MarkdownReference>,
     },
+    Steps {
+        #[serde(skip)]
+        link_id: M::Meta<Uuid>,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "M::skip_data")]
+        steps: M::Data<Vec<Step>>,
+    },
+    Image {
+        #[serde(skip)]
+        link_id: M::Meta<Uuid>,
+        #[serde(flatten)]
+        image: M::Data<ImageReference>,
+    },
+    PageReference {
+        #[serde(skip)]
+        link_id: M::Meta<Uuid>,
+        #[serde(flatten)]
+        page: M::Data<PageReference>,
+    },
+    Checklist {
+        #[serde(skip)]
+        link_id: M::Meta<Uuid>,
+        #[serde(flatten)]
+        checklist: M::Data<ChecklistReference>,
+    },
+    Table {
+        #[serde(skip)]
+        link_id: M::Meta<Uuid>,
+        #[serde(flatten)]
+        table: M::Data<TableReference>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Step {
+    pub text: String,
+    // Duration in minutes
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<i32>,
+    // Ingredients of the same page
+    #[serde(default)]
+    pub ingredients: Vec<IngredientReference>,
+}
+
+impl FromRow<'_, PgRow> for Step {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            text: row.try_get("step_text")?,
+            duration: row.try_get("step_duration")?,
+            ingredients: row
+                .try_get::<Vec<Uuid>, _>("step_ingredient_ids")?
+                .into_iter()
+                .map(|id| IngredientReference {
+                    id,
+                    ..Default::default()
+                })
+                .collect(),
+        })
+    }
 }
 
 impl FromRow<'_, PgRow> for Block {
@@ -90,7 +157,7 @@ impl FromRow<'_, PgRow> for Block {
                                 id: row.get("markdown_id"),
                                 data: Some({
                                     let markdown = row.get::<String, _>("markdown");
-                                    let html = markdown_to_html(&markdown);
+                                    let html = markdown_to_html(&markdown, Options::empty());
                                     MarkdownDataTemplate {
                                         markdown: Some(markdown),
                                         html: Some(html),
@@ -99,6 +166,59 @@ impl FromRow<'_, PgRow> for Block {
                                 ..Default::default()
                             },
                         }
+                    } else if let Some(id) = row.get("image_block_id") {
+                        BlockKindTemplate::Image {
+                            link_id: id,
+                            image: ImageReference {
+                                id: row.get("image_id"),
+                                data: Some(ImageDataTemplate {
+                                    name: row.get("image_name"),
+                                    content_type: Some(row.get("image_content_type")),
+                                    size: Some(row.get("image_size")),
+                                    width: Some(row.get("image_width")),
+                                    height: Some(row.get("image_height")),
+                                    thumbnails: Some(row.get("image_thumbnails")),
+                                }),
+                                ..Default::default()
+                            },
+                        }
+                    } else if let Some(id) = row.get("page_reference_block_id") {
+                        BlockKindTemplate::PageReference {
+                            link_id: id,
+                            page: PageReference {
+                                id: row.get("referenced_page_id"),
+                                data: Some(PageDataTemplate {
+                                    r#type: Some(row.get("referenced_page_type")),
+                                    name: Some(row.get("referenced_page_name")),
+                                    ..Default::default()
+                                }),
+                                ..Default::default()
+                            },
+                        }
+                    } else if let Some(id) = row.get("checklist_block_id") {
+                        // Items are available through the checklist itself
+                        BlockKindTemplate::Checklist {
+                            link_id: id,
+                            checklist: ChecklistReference {
+                                id: row.get("checklist_id"),
+                                ..Default::default()
+                            },
+                        }
+                    } else if let Some(id) = row.get("table_block_id") {
+                        // Contents are available through the table itself
+                        BlockKindTemplate::Table {
+                            link_id: id,
+                            table: TableReference {
+                                id: row.get("table_id"),
+                                ..Default::default()
+                            },
+                        }
+                    } else if let Some(id) = row.get("steps_block_id") {
+                        // Steps are collected separately
+                        BlockKindTemplate::Steps {
+                            link_id: id,
+                            steps: Vec::new(),
+                        }
                     } else {
                         panic!("unreachable!")
                     }
@@ -122,7 +242,7 @@ impl BlockDb for BlockDbPostgres<'_> {
     async fn get_multiple(&mut self) -> Result<Vec<Block>> {
         let mut conn = self.pool.acquire().await?;
 
-        sqlx::query_as(
+        let mut items: Vec<Block> = sqlx::query_as(
             "
             SELECT
                 blocks.id,
@@ -132,7 +252,24 @@ impl BlockDb for BlockDbPostgres<'_> {
                 ingredient_collections.id AS ingredient_collection_id,
                 markdown_blocks.id AS markdown_block_id,
                 markdown.id AS markdown_id,
-                markdown.markdown AS markdown
+                markdown.markdown AS markdown,
+                blocks.steps_block_id,
+                image_blocks.id AS image_block_id,
+                images.id AS image_id,
+                images.name AS image_name,
+                images.content_type AS image_content_type,
+                images.size AS image_size,
+                images.width AS image_width,
+                images.height AS image_height,
+                images.thumbnails AS image_thumbnails,
+                page_reference_blocks.id AS page_reference_block_id,
+                referenced_pages.id AS referenced_page_id,
+                referenced_pages.type AS referenced_page_type,
+                referenced_pages.name AS referenced_page_name,
+                checklist_blocks.id AS checklist_block_id,
+                checklist_blocks.checklist_id AS checklist_id,
+                table_blocks.id AS table_block_id,
+                table_blocks.table_id AS table_id
 
             FROM public.blocks
                 LEFT JOIN public.ingredient_collection_blocks
@@ -145,19 +282,38 @@ impl BlockDb for BlockDbPostgres<'_> {
                 LEFT JOIN public.markdown
                     ON markdown_blocks.markdown_id = markdown.id
 
+                LEFT JOIN public.image_blocks
+                    ON blocks.image_block_id = image_blocks.id
+                LEFT JOIN public.images
+                    ON image_blocks.image_id = images.id
+
+                LEFT JOIN public.page_reference_blocks
+                    ON blocks.page_reference_block_id = page_reference_blocks.id
+                LEFT JOIN public.pages AS referenced_pages
+                    ON page_reference_blocks.page_id = referenced_pages.id
+
+                LEFT JOIN public.checklist_blocks
+                    ON blocks.checklist_block_id = checklist_blocks.id
+
+                LEFT JOIN public.table_blocks
+                    ON blocks.table_block_id = table_blocks.id
+
             ORDER BY blocks.id
             ",
         )
         .fetch(&mut *conn)
         .try_collect()
-        .map_err(|error| error.into())
-        .await
+        .await?;
+
+        Self::collect_steps(&mut conn, &mut items).await?;
+
+        Ok(items)
     }
 
     async fn get_by_id(&mut self, id: &Uuid) -> Result<Block> {
         let mut conn = self.pool.acquire().await?;
 
-        Self::get_by_id(&mut *conn, id).await
+        Self::get_by_id(&mut conn, id).await
     }
 
     async fn create_multiple(&mut self, items: Vec<BlockCreate>) -> Result<Vec<Block>> {
@@ -196,7 +352,26 @@ impl BlockDb for BlockDbPostgres<'_> {
     }
 
     async fn delete_by_id(&mut self, id: &Uuid) -> Result<()> {
-        let mut conn = self.pool.acquire().await?;
+        let mut tx = self.pool.begin().await?;
+
+        match Self::delete_by_id(&mut tx, id).await {
+            Ok(()) => {}
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+impl BlockDbPostgres<'_> {
+    async fn delete_by_id(tx: &mut PgTransaction<'_>, id: &Uuid) -> Result<()> {
+        // Pages lose the block along with its links
+        let page_ids = PageRevisionDbPostgres::pages_with_block(tx, id).await?;
 
         // Relying on SQL trigger to delete corresponding block types (markdown, etc)
         if sqlx::query(
@@ -206,7 +381,7 @@ impl BlockDb for BlockDbPostgres<'_> {
             ",
         )
         .bind(id)
-        .execute(&mut *conn)
+        .execute(&mut **tx)
         .await?
         .rows_affected()
             == 0
@@ -214,16 +389,14 @@ impl BlockDb for BlockDbPostgres<'_> {
             return Err((DbError::NotFound).into());
         }
 
-        Ok(())
+        // Removing a block may leave a page without a block its type requires
+        PageDbPostgres::validate_pages(tx, &page_ids).await?;
+
+        PageRevisionDbPostgres::record_multiple(tx, &page_ids).await
     }
-}
 
-impl BlockDbPostgres<'_> {
-    async fn get_by_id<'c, E>(executor: E, id: &Uuid) -> Result<Block>
-    where
-        E: PgExecutor<'c>,
-    {
-        sqlx::query_as(
+    async fn get_by_id(conn: &mut PgConnection, id: &Uuid) -> Result<Block> {
+        let item = sqlx::query_as(
             "
             SELECT
                 blocks.id,
@@ -233,7 +406,24 @@ impl BlockDbPostgres<'_> {
                 ingredient_collections.id AS ingredient_collection_id,
                 markdown_blocks.id AS markdown_block_id,
                 markdown.id AS markdown_id,
-                markdown.markdown AS markdown
+                markdown.markdown AS markdown,
+                blocks.steps_block_id,
+                image_blocks.id AS image_block_id,
+                images.id AS image_id,
+                images.name AS image_name,
+                images.content_type AS image_content_type,
+                images.size AS image_size,
+                images.width AS image_width,
+                images.height AS image_height,
+                images.thumbnails AS image_thumbnails,
+                page_reference_blocks.id AS page_reference_block_id,
+                referenced_pages.id AS referenced_page_id,
+                referenced_pages.type AS referenced_page_type,
+                referenced_pages.name AS referenced_page_name,
+                checklist_blocks.id AS checklist_block_id,
+                checklist_blocks.checklist_id AS checklist_id,
+                table_blocks.id AS table_block_id,
+                table_blocks.table_id AS table_id
 
             FROM public.blocks
                 LEFT JOIN public.ingredient_collection_blocks
@@ -246,19 +436,129 @@ impl BlockDbPostgres<'_> {
                 LEFT JOIN public.markdown
                     ON markdown_blocks.markdown_id = markdown.id
 
+                LEFT JOIN public.image_blocks
+                    ON blocks.image_block_id = image_blocks.id
+                LEFT JOIN public.images
+                    ON image_blocks.image_id = images.id
+
+                LEFT JOIN public.page_reference_blocks
+                    ON blocks.page_reference_block_id = page_reference_blocks.id
+                LEFT JOIN public.pages AS referenced_pages
+                    ON page_reference_blocks.page_id = referenced_pages.id
+
+                LEFT JOIN public.checklist_blocks
+                    ON blocks.checklist_block_id = checklist_blocks.id
+
+                LEFT JOIN public.table_blocks
+                    ON blocks.table_block_id = table_blocks.id
+
             WHERE blocks.id = $1
             ",
         )
         .bind(id)
-        .fetch_one(executor)
+        .fetch_one(&mut *conn)
         .map_err(|error| match error {
             sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::NotFound),
             _ => error.into(),
         })
-        .await
+        .await?;
+
+        let mut items = [item];
+        Self::collect_steps(conn, &mut items).await?;
+        let [item] = items;
+
+        Ok(item)
+    }
+
+    async fn collect_steps(conn: &mut PgConnection, items: &mut [Block]) -> Result<()> {
+        let link_ids: Vec<Uuid> = items
+            .iter()
+            .filter_map(|item| match &item.data.kind {
+                BlockKindTemplate::Steps { link_id, .. } => Some(*link_id),
+                _ => None,
+            })
+            .collect();
+
+        if link_ids.is_empty() {
+            return Ok(());
+        }
+
+        let rows = sqlx::query(
+            "
+            SELECT
+                steps.steps_block_id,
+                steps.text AS step_text,
+                steps.duration AS step_duration,
+                ARRAY(
+                    SELECT ingredient_id
+                    FROM public.step_ingredients
+                    WHERE step_id = steps.id
+                    ORDER BY sequence_number
+                ) AS step_ingredient_ids
+
+            FROM public.steps
+            WHERE steps.steps_block_id = ANY($1)
+            ORDER BY steps.sequence_number
+            ",
+        )
+        .bind(&link_ids)
+        .fetch_all(conn)
+        .await?;
+
+        for item in items {
+            if let BlockKindTemplate::Steps { link_id, steps } = &mut item.data.kind {
+                for row in &rows {
+                    if row.get::<Uuid, _>("steps_block_id") == *link_id {
+                        steps.push(Step::from_row(row)?);
+                    }
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    async fn create(tx: &mut PgTransaction<'_>, create: BlockCreate) -> Result<Block> {
+    pub async fn insert_steps(
+        tx: &mut PgTransaction<'_>,
+        link_id: &Uuid,
+        steps: &[Step],
+    ) -> Result<()> {
+        for (seq, step) in steps.iter().enumerate() {
+            let step_id = Uuid::new_v4();
+            sqlx::query(
+                "
+                INSERT INTO public.steps (id, steps_block_id, sequence_number, text, duration)
+                VALUES ($1, $2, $3, $4, $5)
+                ",
+            )
+            .bind(step_id)
+            .bind(link_id)
+            .bind(seq as i32)
+            .bind(&step.text)
+            .bind(step.duration)
+            .execute(&mut **tx)
+            .await?;
+
+            for (seq, ingredient) in step.ingredients.iter().enumerate() {
+                sqlx::query(
+                    "
+                    INSERT INTO public.step_ingredients (step_id, ingredient_id, sequence_number)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT DO NOTHING
+                    ",
+                )
+                .bind(step_id)
+                .bind(ingredient.id)
+                .bind(seq as i32)
+                .execute(&mut **tx)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn create(tx: &mut PgTransaction<'_>, create: BlockCreate) -> Result<Block> {
         match create.kind {
             BlockKindTemplate::IngredientCollection {
                 ingredient_collection,
@@ -346,6 +646,211 @@ impl BlockDbPostgres<'_> {
                     },
                 })
             }
+
+            BlockKindTemplate::Image { image, .. } => {
+                let link_id = Uuid::new_v4();
+                let _ = sqlx::query(
+                    "
+                    INSERT INTO public.image_blocks (id, image_id)
+                    VALUES ($1, $2)
+                    ",
+                )
+                .bind(link_id)
+                .bind(image.id)
+                .execute(&mut **tx)
+                .await?;
+
+                let item_id = Uuid::new_v4();
+                let item = sqlx::query(
+                    "
+                    INSERT INTO public.blocks (id, image_block_id)
+                    VALUES ($1, $2)
+                    RETURNING ts_created
+                    ",
+                )
+                .bind(item_id)
+                .bind(link_id)
+                .fetch_one(&mut **tx)
+                .await?;
+
+                Ok(Block {
+                    id: item_id,
+                    ts_created: item.get("ts_created"),
+                    ts_updated: None,
+                    data: BlockDataTemplate {
+                        kind: BlockKindTemplate::Image {
+                            link_id,
+                            image: ImageReference {
+                                id: image.id,
+                                ..Default::default()
+                            },
+                        },
+                    },
+                })
+            }
+
+            BlockKindTemplate::PageReference { page, .. } => {
+                let link_id = Uuid::new_v4();
+                let _ = sqlx::query(
+                    "
+                    INSERT INTO public.page_reference_blocks (id, page_id)
+                    VALUES ($1, $2)
+                    ",
+                )
+                .bind(link_id)
+                .bind(page.id)
+                .execute(&mut **tx)
+                .await?;
+
+                let item_id = Uuid::new_v4();
+                let item = sqlx::query(
+                    "
+                    INSERT INTO public.blocks (id, page_reference_block_id)
+                    VALUES ($1, $2)
+                    RETURNING ts_created
+                    ",
+                )
+                .bind(item_id)
+                .bind(link_id)
+                .fetch_one(&mut **tx)
+                .await?;
+
+                Ok(Block {
+                    id: item_id,
+                    ts_created: item.get("ts_created"),
+                    ts_updated: None,
+                    data: BlockDataTemplate {
+                        kind: BlockKindTemplate::PageReference {
+                            link_id,
+                            page: PageReference {
+                                id: page.id,
+                                ..Default::default()
+                            },
+                        },
+                    },
+                })
+            }
+
+            BlockKindTemplate::Checklist { checklist, .. } => {
+                let link_id = Uuid::new_v4();
+                let _ = sqlx::query(
+                    "
+                    INSERT INTO public.checklist_blocks (id, checklist_id)
+                    VALUES ($1, $2)
+                    ",
+                )
+                .bind(link_id)
+                .bind(checklist.id)
+                .execute(&mut **tx)
+                .await?;
+
+                let item_id = Uuid::new_v4();
+                let item = sqlx::query(
+                    "
+                    INSERT INTO public.blocks (id, checklist_block_id)
+                    VALUES ($1, $2)
+                    RETURNING ts_created
+                    ",
+                )
+                .bind(item_id)
+                .bind(link_id)
+                .fetch_one(&mut **tx)
+                .await?;
+
+                Ok(Block {
+                    id: item_id,
+                    ts_created: item.get("ts_created"),
+                    ts_updated: None,
+                    data: BlockDataTemplate {
+                        kind: BlockKindTemplate::Checklist {
+                            link_id,
+                            checklist: ChecklistReference {
+                                id: checklist.id,
+                                ..Default::default()
+                            },
+                        },
+                    },
+                })
+            }
+
+            BlockKindTemplate::Table { table, .. } => {
+                let link_id = Uuid::new_v4();
+                let _ = sqlx::query(
+                    "
+                    INSERT INTO public.table_blocks (id, table_id)
+                    VALUES ($1, $2)
+                    ",
+                )
+                .bind(link_id)
+                .bind(table.id)
+                .execute(&mut **tx)
+                .await?;
+
+                let item_id = Uuid::new_v4();
+                let item = sqlx::query(
+                    "
+                    INSERT INTO public.blocks (id, table_block_id)
+                    VALUES ($1, $2)
+                    RETURNING ts_created
+                    ",
+                )
+                .bind(item_id)
+                .bind(link_id)
+                .fetch_one(&mut **tx)
+                .await?;
+
+                Ok(Block {
+                    id: item_id,
+                    ts_created: item.get("ts_created"),
+                    ts_updated: None,
+                    data: BlockDataTemplate {
+                        kind: BlockKindTemplate::Table {
+                            link_id,
+                            table: TableReference {
+                                id: table.id,
+                                ..Default::default()
+                            },
+                        },
+                    },
+                })
+            }
+
+            BlockKindTemplate::Steps { steps, .. } => {
+                let link_id = Uuid::new_v4();
+                let _ = sqlx::query(
+                    "
+                    INSERT INTO public.steps_blocks (id)
+                    VALUES ($1)
+                    ",
+                )
+                .bind(link_id)
+                .execute(&mut **tx)
+                .await?;
+
+                Self::insert_steps(tx, &link_id, &steps).await?;
+
+                let item_id = Uuid::new_v4();
+                let item = sqlx::query(
+                    "
+                    INSERT INTO public.blocks (id, steps_block_id)
+                    VALUES ($1, $2)
+                    RETURNING ts_created
+                    ",
+                )
+                .bind(item_id)
+                .bind(link_id)
+                .fetch_one(&mut **tx)
+                .await?;
+
+                Ok(Block {
+                    id: item_id,
+                    ts_created: item.get("ts_created"),
+                    ts_updated: None,
+                    data: BlockDataTemplate {
+                        kind: BlockKindTemplate::Steps { link_id, steps },
+                    },
+                })
+            }
         }
     }
 
@@ -354,7 +859,7 @@ impl BlockDbPostgres<'_> {
         id: &Uuid,
         update: BlockUpdate,
     ) -> Result<Block> {
-        let mut item = Self::get_by_id(&mut **tx, id).await?;
+        let mut item = Self::get_by_id(tx, id).await?;
 
         match &mut item.data.kind {
             BlockKindTemplate::IngredientCollection {
@@ -427,6 +932,185 @@ impl BlockDbPostgres<'_> {
                 // Nothing to update
                 _ => {}
             },
+
+            BlockKindTemplate::Image {
+                link_id,
+                image: current,
+            } => match update.kind {
+                Some(BlockKindTemplate::Image { image: update, .. }) => {
+                    if let Some(update) = update {
+                        current.id = update.id
+                    }
+
+                    sqlx::query(
+                        "
+                         UPDATE public.image_blocks
+                         SET image_id = $2,
+                             ts_updated = NOW()
+                         WHERE id = $1
+                         ",
+                    )
+                    .bind(*link_id)
+                    .bind(current.id)
+                    .execute(&mut **tx)
+                    .await?;
+
+                    // Data might have been invalidated, just leave it out
+                    current.data = None;
+                }
+
+                // List item type cannot be changed
+                Some(_) => return Err((DbError::InvalidOperation).into()),
+
+                // Nothing to update
+                _ => {}
+            },
+
+            BlockKindTemplate::PageReference {
+                link_id,
+                page: current,
+            } => match update.kind {
+                Some(BlockKindTemplate::PageReference { page: update, .. }) => {
+                    if let Some(update) = update {
+                        current.id = update.id
+                    }
+
+                    sqlx::query(
+                        "
+                         UPDATE public.page_reference_blocks
+                         SET page_id = $2,
+                             ts_updated = NOW()
+                         WHERE id = $1
+                         ",
+                    )
+                    .bind(*link_id)
+                    .bind(current.id)
+                    .execute(&mut **tx)
+                    .await?;
+
+                    // Data might have been invalidated, just leave it out
+                    current.data = None;
+
+                    // The new reference must not introduce a cycle
+                    PageDbPostgres::validate_pages_with_block(tx, id).await?;
+                }
+
+                // List item type cannot be changed
+                Some(_) => return Err((DbError::InvalidOperation).into()),
+
+                // Nothing to update
+                _ => {}
+            },
+
+            BlockKindTemplate::Checklist {
+                link_id,
+                checklist: current,
+            } => match update.kind {
+                Some(BlockKindTemplate::Checklist {
+                    checklist: update, ..
+                }) => {
+                    if let Some(update) = update {
+                        current.id = update.id
+                    }
+
+                    sqlx::query(
+                        "
+                         UPDATE public.checklist_blocks
+                         SET checklist_id = $2,
+                             ts_updated = NOW()
+                         WHERE id = $1
+                         ",
+                    )
+                    .bind(*link_id)
+                    .bind(current.id)
+                    .execute(&mut **tx)
+                    .await?;
+
+                    // Data might have been invalidated, just leave it out
+                    current.data = None;
+                }
+
+                // List item type cannot be changed
+                Some(_) => return Err((DbError::InvalidOperation).into()),
+
+                // Nothing to update
+                _ => {}
+            },
+
+            BlockKindTemplate::Table {
+                link_id,
+                table: current,
+            } => match update.kind {
+                Some(BlockKindTemplate::Table { table: update, .. }) => {
+                    if let Some(update) = update {
+                        current.id = update.id
+                    }
+
+                    sqlx::query(
+                        "
+                         UPDATE public.table_blocks
+                         SET table_id = $2,
+                             ts_updated = NOW()
+                         WHERE id = $1
+                         ",
+                    )
+                    .bind(*link_id)
+                    .bind(current.id)
+                    .execute(&mut **tx)
+                    .await?;
+
+                    // Data might have been invalidated, just leave it out
+                    current.data = None;
+                }
+
+                // List item type cannot be changed
+                Some(_) => return Err((DbError::InvalidOperation).into()),
+
+                // Nothing to update
+                _ => {}
+            },
+
+            BlockKindTemplate::Steps {
+                link_id,
+                steps: current,
+            } => match update.kind {
+                Some(BlockKindTemplate::Steps { steps: update, .. }) => {
+                    if let Some(update) = update {
+                        sqlx::query(
+                            "
+                            DELETE FROM public.steps
+                            WHERE steps_block_id = $1
+                            ",
+                        )
+                        .bind(*link_id)
+                        .execute(&mut **tx)
+                        .await?;
+
+                        Self::insert_steps(tx, link_id, &update).await?;
+                        *current = update;
+                    }
+
+                    sqlx::query(
+                        "
+                         UPDATE public.steps_blocks
+                         SET ts_updated = NOW()
+                         WHERE id = $1
+                         ",
+                    )
+                    .bind(*link_id)
+                    .execute(&mut **tx)
+                    .await?;
+
+                    // Referenced ingredients must still belong to the pages holding the block
+                    PageDbPostgres::validate_pages_with_block(tx, id).await?;
+                }
+
+                // List item type cannot be changed
+                Some(_) => return Err((DbError::InvalidOperation).into()),
+
+                // Nothing to update
+                _ => {}
+            },
         };
 
         let row = sqlx::query(
@@ -434,6 +1118,7 @@ impl BlockDbPostgres<'_> {
              UPDATE public.blocks
              SET ts_updated = NOW()
              WHERE id = $1
+             RETURNING ts_updated
              ",
         )
         .bind(id)
@@ -442,6 +1127,8 @@ impl BlockDbPostgres<'_> {
 
         item.ts_updated = row.get("ts_updated");
 
+        PageRevisionDbPostgres::record_with_block(tx, id).await?;
+
         Ok(item)
     }
 }
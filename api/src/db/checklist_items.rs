@@ -0,0 +1,297 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{TryFutureExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
+use uuid::Uuid;
+
+use crate::utilities::modifier::{Create, Modifier, Query, Update};
+
+use super::{page_revisions::PageRevisionDbPostgres, DbError};
+
+#[trait_variant::make(Send)]
+pub trait ChecklistItemDb {
+    async fn get_multiple(&mut self, checklist_id: &Uuid) -> Result<Vec<ChecklistItem>>;
+    async fn get_by_id(&mut self, checklist_id: &Uuid, id: &Uuid) -> Result<ChecklistItem>;
+    async fn create_multiple(
+        &mut self,
+        checklist_id: &Uuid,
+        items: Vec<ChecklistItemCreate>,
+    ) -> Result<Vec<ChecklistItem>>;
+    async fn update_by_id(
+        &mut self,
+        checklist_id: &Uuid,
+        id: &Uuid,
+        item: ChecklistItemUpdate,
+    ) -> Result<ChecklistItem>;
+    async fn delete_by_id(&mut self, checklist_id: &Uuid, id: &Uuid) -> Result<()>;
+}
+
+pub type ChecklistItem = ChecklistItemTemplate<Query>;
+pub type ChecklistItemCreate = ChecklistItemDataTemplate<Create>;
+pub type ChecklistItemUpdate = ChecklistItemDataTemplate<Update>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ChecklistItemTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<ChecklistItemDataTemplate<M>>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ChecklistItemDataTemplate<M: Modifier> {
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub text: M::Data<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub checked: M::Data<bool>,
+}
+
+impl FromRow<'_, PgRow> for ChecklistItem {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            ts_created: row.try_get("ts_created")?,
+            ts_updated: row.try_get("ts_updated")?,
+            data: ChecklistItemDataTemplate {
+                text: row.try_get("text")?,
+                checked: row.try_get("checked")?,
+            },
+        })
+    }
+}
+
+pub struct ChecklistItemDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ChecklistItemDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ChecklistItemDb for ChecklistItemDbPostgres<'_> {
+    async fn get_multiple(&mut self, checklist_id: &Uuid) -> Result<Vec<ChecklistItem>> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated, text, checked
+            FROM public.checklist_items
+            WHERE checklist_id = $1
+            ORDER BY sequence_number
+            ",
+        )
+        .bind(checklist_id)
+        .fetch(&mut *conn)
+        .try_collect()
+        .map_err(|error| error.into())
+        .await
+    }
+
+    async fn get_by_id(&mut self, checklist_id: &Uuid, id: &Uuid) -> Result<ChecklistItem> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut *conn, checklist_id, id).await
+    }
+
+    async fn create_multiple(
+        &mut self,
+        checklist_id: &Uuid,
+        items: Vec<ChecklistItemCreate>,
+    ) -> Result<Vec<ChecklistItem>> {
+        let mut tx = self.pool.begin().await?;
+        let mut created = Vec::new();
+
+        for item in items {
+            match Self::create(&mut tx, checklist_id, item).await {
+                Ok(item) => created.push(item),
+                Err(error) => {
+                    tx.rollback().await?;
+                    return Err(error);
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn update_by_id(
+        &mut self,
+        checklist_id: &Uuid,
+        id: &Uuid,
+        item: ChecklistItemUpdate,
+    ) -> Result<ChecklistItem> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = match Self::update_by_id(&mut tx, checklist_id, id, item).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn delete_by_id(&mut self, checklist_id: &Uuid, id: &Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        match Self::delete_by_id(&mut tx, checklist_id, id).await {
+            Ok(()) => {}
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+impl ChecklistItemDbPostgres<'_> {
+    async fn get_by_id<'c, E>(executor: E, checklist_id: &Uuid, id: &Uuid) -> Result<ChecklistItem>
+    where
+        E: PgExecutor<'c>,
+    {
+        sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated, text, checked
+            FROM public.checklist_items
+            WHERE checklist_id = $1 AND id = $2
+            ",
+        )
+        .bind(checklist_id)
+        .bind(id)
+        .fetch_one(executor)
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::NotFound),
+            _ => error.into(),
+        })
+        .await
+    }
+
+    // New items are appended to the end of the checklist
+    pub async fn create(
+        tx: &mut PgTransaction<'_>,
+        checklist_id: &Uuid,
+        create: ChecklistItemCreate,
+    ) -> Result<ChecklistItem> {
+        sqlx::query(
+            "
+            SELECT id
+            FROM public.checklists
+            WHERE id = $1
+            ",
+        )
+        .bind(checklist_id)
+        .fetch_one(&mut **tx)
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::NotFound),
+            _ => error.into(),
+        })
+        .await?;
+
+        let item = sqlx::query_as(
+            "
+            INSERT INTO public.checklist_items (id, checklist_id, sequence_number, text, checked)
+            VALUES (
+                $1,
+                $2,
+                (
+                    SELECT COALESCE(MAX(sequence_number) + 1, 0)
+                    FROM public.checklist_items
+                    WHERE checklist_id = $2
+                ),
+                $3,
+                $4
+            )
+            RETURNING id, ts_created, ts_updated, text, checked
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(checklist_id)
+        .bind(create.text)
+        .bind(create.checked)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        PageRevisionDbPostgres::record_with_checklist(tx, checklist_id).await?;
+
+        Ok(item)
+    }
+
+    async fn update_by_id(
+        tx: &mut PgTransaction<'_>,
+        checklist_id: &Uuid,
+        id: &Uuid,
+        update: ChecklistItemUpdate,
+    ) -> Result<ChecklistItem> {
+        let mut item = Self::get_by_id(&mut **tx, checklist_id, id).await?;
+
+        if let Some(text) = update.text {
+            item.data.text = text;
+        }
+        if let Some(checked) = update.checked {
+            item.data.checked = checked;
+        }
+
+        let row = sqlx::query(
+            "
+            UPDATE public.checklist_items
+            SET text = $2,
+                checked = $3,
+                ts_updated = NOW()
+            WHERE id = $1
+            RETURNING ts_updated
+            ",
+        )
+        .bind(id)
+        .bind(&item.data.text)
+        .bind(item.data.checked)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        item.ts_updated = row.get("ts_updated");
+
+        PageRevisionDbPostgres::record_with_checklist(tx, checklist_id).await?;
+
+        Ok(item)
+    }
+
+    async fn delete_by_id(
+        tx: &mut PgTransaction<'_>,
+        checklist_id: &Uuid,
+        id: &Uuid,
+    ) -> Result<()> {
+        if sqlx::query(
+            "
+            DELETE FROM public.checklist_items
+            WHERE checklist_id = $1 AND id = $2
+            ",
+        )
+        .bind(checklist_id)
+        .bind(id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        PageRevisionDbPostgres::record_with_checklist(tx, checklist_id).await
+    }
+}
@@ -0,0 +1,209 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{TryFutureExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgConnection, PgPool, PgTransaction, Row};
+use uuid::Uuid;
+
+use crate::utilities::modifier::{Modifier, Query, Reference};
+
+use super::{
+    checklist_items::{ChecklistItem, ChecklistItemCreate, ChecklistItemDbPostgres},
+    DbError,
+};
+
+// Items are edited through their own endpoints
+#[trait_variant::make(Send)]
+pub trait ChecklistDb {
+    async fn get_multiple(&mut self) -> Result<Vec<Checklist>>;
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<Checklist>;
+    async fn create_multiple(&mut self, items: Vec<ChecklistCreate>) -> Result<Vec<Checklist>>;
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
+}
+
+pub type Checklist = ChecklistTemplate<Query>;
+pub type ChecklistReference = ChecklistTemplate<Reference>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ChecklistTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<ChecklistData>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ChecklistData {
+    #[serde(skip_deserializing)]
+    pub items: Vec<ChecklistItem>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ChecklistCreate {
+    #[serde(default)]
+    pub items: Vec<ChecklistItemCreate>,
+}
+
+impl FromRow<'_, PgRow> for Checklist {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            ts_created: row.get("ts_created"),
+            ts_updated: row.get("ts_updated"),
+            // Items are collected separately
+            data: ChecklistData::default(),
+        })
+    }
+}
+
+pub struct ChecklistDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ChecklistDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ChecklistDb for ChecklistDbPostgres<'_> {
+    async fn get_multiple(&mut self) -> Result<Vec<Checklist>> {
+        let mut conn = self.pool.acquire().await?;
+
+        let mut items: Vec<Checklist> = sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated
+            FROM public.checklists
+            ORDER BY ts_created, id
+            ",
+        )
+        .fetch(&mut *conn)
+        .try_collect()
+        .await?;
+
+        Self::collect_items(&mut conn, &mut items).await?;
+
+        Ok(items)
+    }
+
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<Checklist> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut conn, id).await
+    }
+
+    async fn create_multiple(&mut self, items: Vec<ChecklistCreate>) -> Result<Vec<Checklist>> {
+        let mut tx = self.pool.begin().await?;
+        let mut created = Vec::new();
+
+        for item in items {
+            match Self::create(&mut tx, item).await {
+                Ok(item) => created.push(item),
+                Err(error) => {
+                    tx.rollback().await?;
+                    return Err(error);
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        if sqlx::query(
+            "
+            DELETE FROM public.checklists
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        Ok(())
+    }
+}
+
+impl ChecklistDbPostgres<'_> {
+    async fn get_by_id(conn: &mut PgConnection, id: &Uuid) -> Result<Checklist> {
+        let item = sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated
+            FROM public.checklists
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .fetch_one(&mut *conn)
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::NotFound),
+            _ => error.into(),
+        })
+        .await?;
+
+        let mut items = [item];
+        Self::collect_items(conn, &mut items).await?;
+        let [item] = items;
+
+        Ok(item)
+    }
+
+    async fn collect_items(conn: &mut PgConnection, items: &mut [Checklist]) -> Result<()> {
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+
+        let rows = sqlx::query(
+            "
+            SELECT id, ts_created, ts_updated, checklist_id, text, checked
+            FROM public.checklist_items
+            WHERE checklist_id = ANY($1)
+            ORDER BY sequence_number
+            ",
+        )
+        .bind(&ids)
+        .fetch_all(conn)
+        .await?;
+
+        for item in items {
+            for row in &rows {
+                if row.get::<Uuid, _>("checklist_id") == item.id {
+                    item.data.items.push(ChecklistItem::from_row(row)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn create(tx: &mut PgTransaction<'_>, create: ChecklistCreate) -> Result<Checklist> {
+        let mut item: Checklist = sqlx::query_as(
+            "
+            INSERT INTO public.checklists (id)
+            VALUES ($1)
+            RETURNING id, ts_created, ts_updated
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .fetch_one(&mut **tx)
+        .await?;
+
+        for create in create.items {
+            item.data
+                .items
+                .push(ChecklistItemDbPostgres::create(tx, &item.id, create).await?);
+        }
+
+        Ok(item)
+    }
+}
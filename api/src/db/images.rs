@@ -0,0 +1,174 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{TryFutureExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, Row};
+use uuid::Uuid;
+
+use crate::utilities::modifier::{Create, Modifier, Query, Reference};
+
+use super::DbError;
+
+// Only metadata is kept here, the image itself lives in storage
+#[trait_variant::make(Send)]
+pub trait ImageDb {
+    async fn get_multiple(&mut self) -> Result<Vec<Image>>;
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<Image>;
+    async fn create(&mut self, item: ImageCreate) -> Result<Image>;
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
+}
+
+pub type Image = ImageTemplate<Query>;
+pub type ImageCreate = ImageDataTemplate<Create>;
+pub type ImageReference = ImageTemplate<Reference>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ImageTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<ImageDataTemplate<M>>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ImageDataTemplate<M: Modifier> {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub name: M::Nullable<String>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub content_type: M::Data<String>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub size: M::Data<i64>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub width: M::Data<i32>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub height: M::Data<i32>,
+    // Widths of the available thumbnails
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub thumbnails: M::Data<Vec<i32>>,
+}
+
+impl FromRow<'_, PgRow> for Image {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            ts_created: row.get("ts_created"),
+            ts_updated: row.get("ts_updated"),
+            data: ImageDataTemplate {
+                name: row.get("name"),
+                content_type: row.get("content_type"),
+                size: row.get("size"),
+                width: row.get("width"),
+                height: row.get("height"),
+                thumbnails: row.get("thumbnails"),
+            },
+        })
+    }
+}
+
+pub struct ImageDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ImageDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ImageDb for ImageDbPostgres<'_> {
+    async fn get_multiple(&mut self) -> Result<Vec<Image>> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated, name, content_type, size, width, height, thumbnails
+            FROM public.images
+            ORDER BY ts_created, id
+            ",
+        )
+        .fetch(&mut *conn)
+        .try_collect()
+        .map_err(|error| error.into())
+        .await
+    }
+
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<Image> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut *conn, id).await
+    }
+
+    async fn create(&mut self, item: ImageCreate) -> Result<Image> {
+        let mut conn = self.pool.acquire().await?;
+
+        Ok(sqlx::query_as(
+            "
+            INSERT INTO public.images (id, name, content_type, size, width, height, thumbnails)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, ts_created, ts_updated, name, content_type, size, width, height, thumbnails
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(item.name)
+        .bind(item.content_type)
+        .bind(item.size)
+        .bind(item.width)
+        .bind(item.height)
+        .bind(item.thumbnails)
+        .fetch_one(&mut *conn)
+        .await?)
+    }
+
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        if sqlx::query(
+            "
+            DELETE FROM public.images
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::Database(ref database) if database.is_foreign_key_violation() => {
+                Into::<anyhow::Error>::into(DbError::InvalidOperation)
+            }
+            _ => error.into(),
+        })?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        Ok(())
+    }
+}
+
+impl ImageDbPostgres<'_> {
+    async fn get_by_id<'c, E>(executor: E, id: &Uuid) -> Result<Image>
+    where
+        E: PgExecutor<'c>,
+    {
+        sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated, name, content_type, size, width, height, thumbnails
+            FROM public.images
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .fetch_one(executor)
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::NotFound),
+            _ => error.into(),
+        })
+        .await
+    }
+}
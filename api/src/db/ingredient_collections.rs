@@ -82,6 +82,8 @@ impl FromRow<'_, PgRow> for IngredientReference {
                     }),
                     ..Default::default()
                 }),
+                quantity: row.get("quantity"),
+                unit: row.get("unit"),
                 ..Default::default()
             }),
             ..Default::default()
@@ -149,6 +151,8 @@ impl IngredientCollectionDb for IngredientCollectionDbPostgres<'_> {
                 ingredient_collections.ts_created,
                 ingredient_collections.ts_updated,
                 ingredients.id AS ingredient_id,
+                ingredients.quantity,
+                ingredients.unit,
                 products.id AS product_id,
                 products.name AS product_name
 
@@ -175,6 +179,8 @@ impl IngredientCollectionDb for IngredientCollectionDbPostgres<'_> {
                 ingredient_collections.ts_created,
                 ingredient_collections.ts_updated,
                 ingredients.id AS ingredient_id,
+                ingredients.quantity,
+                ingredients.unit,
                 products.id AS product_id,
                 products.name AS product_name
 
@@ -261,7 +267,7 @@ impl IngredientCollectionDb for IngredientCollectionDbPostgres<'_> {
 }
 
 impl IngredientCollectionDbPostgres<'_> {
-    async fn create(
+    pub async fn create(
         tx: &mut PgTransaction<'_>,
         _create: IngredientCollectionCreate,
     ) -> Result<IngredientCollection> {
@@ -10,6 +10,7 @@ use crate::utilities::modifier::{Create, Modifier, Query, Reference, Update};
 use super::{
     ingredient_collections::IngredientCollectionReference,
     lists::ListReference,
+    page_revisions::PageRevisionDbPostgres,
     products::{ProductDataTemplate, ProductReference},
     DbError,
 };
@@ -52,6 +53,12 @@ pub struct IngredientTemplate<M: Modifier> {
 pub struct IngredientDataTemplate<M: Modifier> {
     #[serde(skip_serializing_if = "M::skip_data")]
     pub product: M::Data<ProductReference>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub quantity: M::Nullable<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub unit: M::Nullable<String>,
     #[serde(skip_deserializing)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub collection_reference: Option<IngredientCollectionReference>,
@@ -75,6 +82,8 @@ impl FromRow<'_, PgRow> for Ingredient {
                     }),
                     ..Default::default()
                 },
+                quantity: row.get("quantity"),
+                unit: row.get("unit"),
                 ..Default::default()
             },
         })
@@ -101,6 +110,8 @@ impl IngredientDb for IngredientDbPostgres<'_> {
                 ingredients.id,
                 ingredients.ingredient_collection_id,
                 ingredients.product_id,
+                ingredients.quantity,
+                ingredients.unit,
                 ingredients.ts_created,
                 ingredients.ts_updated,
                 products.name AS product_name
@@ -144,6 +155,8 @@ impl IngredientDb for IngredientDbPostgres<'_> {
             };
         }
 
+        PageRevisionDbPostgres::record_with_ingredient_collection(&mut tx, collection_id).await?;
+
         tx.commit().await?;
 
         Ok(created)
@@ -171,23 +184,17 @@ impl IngredientDb for IngredientDbPostgres<'_> {
     }
 
     async fn delete_by_id(&mut self, collection_id: &Uuid, id: &Uuid) -> Result<()> {
-        let mut conn = self.pool.acquire().await?;
+        let mut tx = self.pool.begin().await?;
 
-        if sqlx::query(
-            "
-            DELETE FROM public.ingredients
-            WHERE ingredient_collection_id = $1 AND id = $2
-            ",
-        )
-        .bind(collection_id)
-        .bind(id)
-        .execute(&mut *conn)
-        .await?
-        .rows_affected()
-            == 0
-        {
-            return Err((DbError::NotFound).into());
-        }
+        match Self::delete_by_id(&mut tx, collection_id, id).await {
+            Ok(()) => {}
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
 
         Ok(())
     }
@@ -204,6 +211,8 @@ impl IngredientDbPostgres<'_> {
                 ingredients.id,
                 ingredients.ingredient_collection_id,
                 ingredients.product_id,
+                ingredients.quantity,
+                ingredients.unit,
                 ingredients.ts_created,
                 ingredients.ts_updated,
                 products.name AS product_name
@@ -226,7 +235,7 @@ impl IngredientDbPostgres<'_> {
         .await
     }
 
-    async fn create(
+    pub async fn create(
         tx: &mut PgTransaction<'_>,
         collection_id: &Uuid,
         create: IngredientCreate,
@@ -234,14 +243,16 @@ impl IngredientDbPostgres<'_> {
         let item_id = Uuid::new_v4();
         let item = sqlx::query(
             "
-            INSERT INTO public.ingredients (id, ingredient_collection_id, product_id)
-            VALUES ($1, $2, $3)
+            INSERT INTO public.ingredients (id, ingredient_collection_id, product_id, quantity, unit)
+            VALUES ($1, $2, $3, $4, $5)
             RETURNING ts_created, product_id
             ",
         )
         .bind(item_id)
         .bind(collection_id)
         .bind(create.product.id)
+        .bind(create.quantity)
+        .bind(&create.unit)
         .fetch_one(&mut **tx)
         .await?;
 
@@ -254,6 +265,8 @@ impl IngredientDbPostgres<'_> {
                     id: create.product.id,
                     ..Default::default()
                 },
+                quantity: create.quantity,
+                unit: create.unit,
                 ..Default::default()
             },
         })
@@ -270,11 +283,19 @@ impl IngredientDbPostgres<'_> {
         if let Some(product) = update.product {
             item.data.product.id = product.id;
         }
+        if let Some(quantity) = update.quantity.as_ref() {
+            item.data.quantity = quantity.copied();
+        }
+        if let Some(unit) = update.unit.as_ref() {
+            item.data.unit = unit.cloned();
+        }
 
         let row = sqlx::query(
             "
             UPDATE public.ingredients
             SET product_id = $3,
+                quantity = $4,
+                unit = $5,
                 ts_updated = NOW()
             WHERE ingredient_collection_id = $1 AND id = $2
             RETURNING ts_updated
@@ -282,7 +303,9 @@ impl IngredientDbPostgres<'_> {
         )
         .bind(collection_id)
         .bind(id)
-        .bind(item.id)
+        .bind(item.data.product.id)
+        .bind(item.data.quantity)
+        .bind(&item.data.unit)
         .fetch_one(&mut **tx)
         .await?;
 
@@ -290,6 +313,33 @@ impl IngredientDbPostgres<'_> {
         item.data.product.data = None;
         item.ts_updated = row.get("ts_updated");
 
+        PageRevisionDbPostgres::record_with_ingredient_collection(tx, collection_id).await?;
+
         Ok(item)
     }
+
+    // The deletion is only kept along with its revision
+    async fn delete_by_id(
+        tx: &mut PgTransaction<'_>,
+        collection_id: &Uuid,
+        id: &Uuid,
+    ) -> Result<()> {
+        if sqlx::query(
+            "
+            DELETE FROM public.ingredients
+            WHERE ingredient_collection_id = $1 AND id = $2
+            ",
+        )
+        .bind(collection_id)
+        .bind(id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        PageRevisionDbPostgres::record_with_ingredient_collection(tx, collection_id).await
+    }
 }
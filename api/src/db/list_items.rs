@@ -5,12 +5,18 @@ use serde::{Deserialize, Serialize};
 use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
 use uuid::Uuid;
 
-use crate::utilities::modifier::{Create, Modifier, Query, Reference, Update};
+use crate::utilities::{
+    modifier::{Create, Modifier, Query, Reference, Update},
+    quick_add::{self, Fragment},
+};
 
 use super::{
     ingredients::{IngredientDataTemplate, IngredientReference},
     lists::ListReference,
-    products::{ProductDataTemplate, ProductReference},
+    products::{
+        ProductDataTemplate, ProductDbPostgres, ProductReference, SUGGESTION_LIMIT,
+        SUGGESTION_THRESHOLD,
+    },
     DbError,
 };
 
@@ -30,6 +36,29 @@ pub trait ListItemDb {
         item: ListItemUpdate,
     ) -> Result<ListItem>;
     async fn delete_by_id(&mut self, list_id: &Uuid, id: &Uuid) -> Result<()>;
+    async fn batch(
+        &mut self,
+        list_id: &Uuid,
+        operations: Vec<ListItemBatchOperation>,
+    ) -> Result<Vec<ListItem>>;
+    async fn convert_to_product(
+        &mut self,
+        list_id: &Uuid,
+        id: &Uuid,
+        conversion: ListItemConversion,
+    ) -> Result<ListItem>;
+    async fn get_suggestions(&mut self, list_id: &Uuid, id: &Uuid)
+        -> Result<Vec<ProductReference>>;
+    async fn resolve_temporaries(
+        &mut self,
+        list_id: &Uuid,
+        resolution: ListItemResolution,
+    ) -> Result<Vec<ListItem>>;
+    async fn quick_add(
+        &mut self,
+        list_id: &Uuid,
+        quick_add: ListItemQuickAdd,
+    ) -> Result<Vec<ListItemInterpretation>>;
 }
 
 pub type ListItem = ListItemTemplate<Query>;
@@ -44,6 +73,8 @@ pub struct ListItemTemplate<M: Modifier> {
     pub ts_created: M::Meta<DateTime<Utc>>,
     #[serde(skip_serializing_if = "M::skip_meta")]
     pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_checked: M::Meta<Option<DateTime<Utc>>>,
     #[serde(skip_serializing_if = "M::skip_data")]
     pub data: M::Data<ListItemDataTemplate<M>>,
 }
@@ -53,11 +84,23 @@ pub struct ListItemDataTemplate<M: Modifier> {
     #[serde(default)]
     #[serde(skip_serializing_if = "M::skip_data")]
     pub checked: M::Data<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub quantity: M::Nullable<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub unit: M::Nullable<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub note: M::Nullable<String>,
     #[serde(skip_serializing_if = "M::skip_data")]
     pub kind: M::Data<ListItemKindTemplate<M>>,
     #[serde(skip_deserializing)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub list_reference: Option<ListReference>,
+    #[serde(skip_deserializing)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<ProductReference>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -93,14 +136,57 @@ pub struct TemporaryListItemDataTemplate<M: Modifier> {
     pub name: M::Data<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ListItemBatchOperation {
+    DeleteChecked,
+    SetChecked { checked: bool },
+    Delete { ids: Vec<Uuid> },
+    Update { id: Uuid, data: Box<ListItemUpdate> },
+    Move { ids: Vec<Uuid>, list_id: Uuid },
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ListItemConversion {
+    #[serde(default)]
+    pub product: Option<ProductReference>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ListItemResolution {
+    #[serde(default)]
+    pub threshold: Option<f32>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ListItemQuickAdd {
+    pub text: String,
+    #[serde(default)]
+    pub threshold: Option<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListItemInterpretation {
+    #[serde(flatten)]
+    pub fragment: Fragment,
+    pub item: ListItem,
+}
+
+// Minimal similarity for a temporary list item to be resolved without confirmation
+pub const RESOLUTION_THRESHOLD: f32 = 0.6;
+
 impl FromRow<'_, PgRow> for ListItem {
     fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
         Ok(Self {
             id: row.get("id"),
             ts_created: row.get("ts_created"),
             ts_updated: row.get("ts_updated"),
+            ts_checked: row.get("ts_checked"),
             data: ListItemDataTemplate {
                 checked: row.get("checked"),
+                quantity: row.get("quantity"),
+                unit: row.get("unit"),
+                note: row.get("note"),
                 kind: {
                     if let Some(id) = row.get("ingredient_list_item_id") {
                         ListItemKindTemplate::Ingredient {
@@ -139,6 +225,7 @@ impl FromRow<'_, PgRow> for ListItem {
                     }
                 },
                 list_reference: None,
+                suggestions: None,
             },
         })
     }
@@ -158,46 +245,7 @@ impl ListItemDb for ListItemDbPostgres<'_> {
     async fn get_multiple(&mut self, list_id: &Uuid) -> Result<Vec<ListItem>> {
         let mut conn = self.pool.acquire().await?;
 
-        sqlx::query_as(
-            "
-            SELECT
-                list_items.id,
-                list_items.ts_created,
-                list_items.ts_updated,
-                list_items.checked,
-                ingredient_list_items.id AS ingredient_list_item_id,
-                ingredients.id AS ingredient_id,
-                product_list_items.id AS product_list_item_id,
-                products.id AS product_id,
-                products.name AS product_name,
-                temporary_list_items.id AS temporary_list_item_id,
-                temporary_list_items.name AS temporary_list_item_name
-
-            FROM public.list_items
-                LEFT JOIN public.ingredient_list_items
-                    ON list_items.ingredient_list_item_id = ingredient_list_items.id
-                LEFT JOIN public.ingredients
-                    ON ingredient_list_items.ingredient_id = ingredients.id
-
-                LEFT JOIN public.product_list_items
-                    ON list_items.product_list_item_id = product_list_items.id
-                LEFT JOIN public.products
-                    ON product_list_items.product_id = products.id
-
-                LEFT JOIN public.temporary_list_items
-                    ON list_items.temporary_list_item_id = temporary_list_items.id
-
-            WHERE list_items.list_id = $1
-            ORDER BY
-                COALESCE(products.name, temporary_list_items.name),
-                list_items.id
-            ",
-        )
-        .bind(list_id)
-        .fetch(&mut *conn)
-        .try_collect()
-        .map_err(|error| error.into())
-        .await
+        Self::get_multiple(&mut *conn, list_id).await
     }
 
     async fn get_by_id(&mut self, list_id: &Uuid, id: &Uuid) -> Result<ListItem> {
@@ -214,6 +262,11 @@ impl ListItemDb for ListItemDbPostgres<'_> {
         let mut tx = self.pool.begin().await?;
         let mut created = Vec::new();
 
+        if let Err(error) = Self::check_list_exists(&mut tx, list_id).await {
+            tx.rollback().await?;
+            return Err(error);
+        }
+
         for item in items {
             match Self::create(&mut tx, list_id, item).await {
                 Ok(item) => created.push(item),
@@ -272,9 +325,166 @@ impl ListItemDb for ListItemDbPostgres<'_> {
 
         Ok(())
     }
+
+    async fn batch(
+        &mut self,
+        list_id: &Uuid,
+        operations: Vec<ListItemBatchOperation>,
+    ) -> Result<Vec<ListItem>> {
+        let mut tx = self.pool.begin().await?;
+
+        for operation in operations {
+            if let Err(error) = Self::apply(&mut tx, list_id, operation).await {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        }
+
+        let items = Self::get_multiple(&mut *tx, list_id).await?;
+
+        tx.commit().await?;
+
+        Ok(items)
+    }
+
+    async fn convert_to_product(
+        &mut self,
+        list_id: &Uuid,
+        id: &Uuid,
+        conversion: ListItemConversion,
+    ) -> Result<ListItem> {
+        let mut tx = self.pool.begin().await?;
+
+        let converted = match Self::convert_to_product(&mut tx, list_id, id, conversion).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(converted)
+    }
+
+    async fn get_suggestions(
+        &mut self,
+        list_id: &Uuid,
+        id: &Uuid,
+    ) -> Result<Vec<ProductReference>> {
+        let mut conn = self.pool.acquire().await?;
+
+        let item = Self::get_by_id(&mut *conn, list_id, id).await?;
+
+        match item.data.kind {
+            ListItemKindTemplate::Temporary { temporary, .. } => {
+                ProductDbPostgres::get_similar(
+                    &mut conn,
+                    &temporary.data.name,
+                    SUGGESTION_THRESHOLD,
+                    SUGGESTION_LIMIT,
+                )
+                .await
+            }
+
+            // Only temporary list items are in need of suggestions
+            _ => Err((DbError::InvalidOperation).into()),
+        }
+    }
+
+    async fn resolve_temporaries(
+        &mut self,
+        list_id: &Uuid,
+        resolution: ListItemResolution,
+    ) -> Result<Vec<ListItem>> {
+        let mut tx = self.pool.begin().await?;
+
+        let resolved = match Self::resolve_temporaries(&mut tx, list_id, resolution).await {
+            Ok(items) => items,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(resolved)
+    }
+
+    async fn quick_add(
+        &mut self,
+        list_id: &Uuid,
+        quick_add: ListItemQuickAdd,
+    ) -> Result<Vec<ListItemInterpretation>> {
+        let mut tx = self.pool.begin().await?;
+
+        let interpreted = match Self::quick_add(&mut tx, list_id, quick_add).await {
+            Ok(items) => items,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(interpreted)
+    }
 }
 
 impl ListItemDbPostgres<'_> {
+    async fn get_multiple<'c, E>(executor: E, list_id: &Uuid) -> Result<Vec<ListItem>>
+    where
+        E: PgExecutor<'c>,
+    {
+        sqlx::query_as(
+            "
+            SELECT
+                list_items.id,
+                list_items.ts_created,
+                list_items.ts_updated,
+                list_items.ts_checked,
+                list_items.checked,
+                list_items.quantity,
+                list_items.unit,
+                list_items.note,
+                ingredient_list_items.id AS ingredient_list_item_id,
+                ingredients.id AS ingredient_id,
+                product_list_items.id AS product_list_item_id,
+                products.id AS product_id,
+                products.name AS product_name,
+                temporary_list_items.id AS temporary_list_item_id,
+                temporary_list_items.name AS temporary_list_item_name
+
+            FROM public.list_items
+                LEFT JOIN public.ingredient_list_items
+                    ON list_items.ingredient_list_item_id = ingredient_list_items.id
+                LEFT JOIN public.ingredients
+                    ON ingredient_list_items.ingredient_id = ingredients.id
+
+                LEFT JOIN public.product_list_items
+                    ON list_items.product_list_item_id = product_list_items.id
+                LEFT JOIN public.products
+                    ON product_list_items.product_id = products.id
+
+                LEFT JOIN public.temporary_list_items
+                    ON list_items.temporary_list_item_id = temporary_list_items.id
+
+            WHERE list_items.list_id = $1
+            ORDER BY
+                COALESCE(products.name, temporary_list_items.name),
+                list_items.id
+            ",
+        )
+        .bind(list_id)
+        .fetch(executor)
+        .try_collect()
+        .map_err(|error| error.into())
+        .await
+    }
+
     async fn get_by_id<'c, E>(executor: E, list_id: &Uuid, id: &Uuid) -> Result<ListItem>
     where
         E: PgExecutor<'c>,
@@ -285,7 +495,11 @@ impl ListItemDbPostgres<'_> {
                 list_items.id,
                 list_items.ts_created,
                 list_items.ts_updated,
+                list_items.ts_checked,
                 list_items.checked,
+                list_items.quantity,
+                list_items.unit,
+                list_items.note,
                 ingredient_list_items.id AS ingredient_list_item_id,
                 ingredients.id AS ingredient_id,
                 product_list_items.id AS product_list_item_id,
@@ -327,7 +541,7 @@ impl ListItemDbPostgres<'_> {
         .await
     }
 
-    async fn create(
+    pub async fn create(
         tx: &mut PgTransaction<'_>,
         list_id: &Uuid,
         create: ListItemCreate,
@@ -346,18 +560,31 @@ impl ListItemDbPostgres<'_> {
                 .execute(&mut **tx)
                 .await?;
 
+                // Quantity and unit default to those of the ingredient itself
                 let item_id = Uuid::new_v4();
                 let item = sqlx::query(
                     "
-                    INSERT INTO public.list_items (id, list_id, checked, ingredient_list_item_id)
-                    VALUES ($1, $2, $3, $4)
-                    RETURNING ts_created, checked
+                    INSERT INTO public.list_items (
+                        id, list_id, checked, ingredient_list_item_id, quantity, unit, note
+                    )
+                    SELECT
+                        $1, $2, $3, $4,
+                        COALESCE($5::DOUBLE PRECISION, ingredients.quantity),
+                        CASE WHEN $5 IS NULL THEN ingredients.unit ELSE $6 END,
+                        $7
+                    FROM public.ingredients
+                    WHERE ingredients.id = $8
+                    RETURNING ts_created, ts_checked, checked, quantity, unit, note
                     ",
                 )
                 .bind(item_id)
                 .bind(list_id)
                 .bind(create.checked)
                 .bind(link_id)
+                .bind(create.quantity)
+                .bind(&create.unit)
+                .bind(&create.note)
+                .bind(ingredient.id)
                 .fetch_one(&mut **tx)
                 .await?;
 
@@ -365,8 +592,12 @@ impl ListItemDbPostgres<'_> {
                     id: item_id,
                     ts_created: item.get("ts_created"),
                     ts_updated: None,
+                    ts_checked: item.get("ts_checked"),
                     data: ListItemDataTemplate {
                         checked: item.get("checked"),
+                        quantity: item.get("quantity"),
+                        unit: item.get("unit"),
+                        note: item.get("note"),
                         kind: ListItemKindTemplate::Ingredient {
                             link_id,
                             ingredient: IngredientReference {
@@ -375,6 +606,7 @@ impl ListItemDbPostgres<'_> {
                             },
                         },
                         list_reference: None,
+                        suggestions: None,
                     },
                 })
             }
@@ -395,15 +627,20 @@ impl ListItemDbPostgres<'_> {
                 let item_id = Uuid::new_v4();
                 let item = sqlx::query(
                     "
-                    INSERT INTO public.list_items (id, list_id, checked, product_list_item_id)
-                    VALUES ($1, $2, $3, $4)
-                    RETURNING ts_created, checked
+                    INSERT INTO public.list_items (
+                        id, list_id, checked, product_list_item_id, quantity, unit, note
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    RETURNING ts_created, ts_checked, checked, quantity, unit, note
                     ",
                 )
                 .bind(item_id)
                 .bind(list_id)
                 .bind(create.checked)
                 .bind(link_id)
+                .bind(create.quantity)
+                .bind(&create.unit)
+                .bind(&create.note)
                 .fetch_one(&mut **tx)
                 .await?;
 
@@ -411,8 +648,12 @@ impl ListItemDbPostgres<'_> {
                     id: item_id,
                     ts_created: item.get("ts_created"),
                     ts_updated: None,
+                    ts_checked: item.get("ts_checked"),
                     data: ListItemDataTemplate {
                         checked: item.get("checked"),
+                        quantity: item.get("quantity"),
+                        unit: item.get("unit"),
+                        note: item.get("note"),
                         kind: ListItemKindTemplate::Product {
                             link_id,
                             product: ProductReference {
@@ -421,6 +662,7 @@ impl ListItemDbPostgres<'_> {
                             },
                         },
                         list_reference: None,
+                        suggestions: None,
                     },
                 })
             }
@@ -441,24 +683,41 @@ impl ListItemDbPostgres<'_> {
                 let item_id = Uuid::new_v4();
                 let item = sqlx::query(
                     "
-                    INSERT INTO public.list_items (id, list_id, checked, temporary_list_item_id)
-                    VALUES ($1, $2, $3, $4)
-                    RETURNING ts_created, checked
+                    INSERT INTO public.list_items (
+                        id, list_id, checked, temporary_list_item_id, quantity, unit, note
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    RETURNING ts_created, ts_checked, checked, quantity, unit, note
                     ",
                 )
                 .bind(item_id)
                 .bind(list_id)
                 .bind(create.checked)
                 .bind(link_id)
+                .bind(create.quantity)
+                .bind(&create.unit)
+                .bind(&create.note)
                 .fetch_one(&mut **tx)
                 .await?;
 
+                let suggestions = ProductDbPostgres::get_similar(
+                    tx,
+                    &temporary.data.name,
+                    SUGGESTION_THRESHOLD,
+                    SUGGESTION_LIMIT,
+                )
+                .await?;
+
                 Ok(ListItem {
                     id: item_id,
                     ts_created: item.get("ts_created"),
                     ts_updated: None,
+                    ts_checked: item.get("ts_checked"),
                     data: ListItemDataTemplate {
                         checked: item.get("checked"),
+                        quantity: item.get("quantity"),
+                        unit: item.get("unit"),
+                        note: item.get("note"),
                         kind: ListItemKindTemplate::Temporary {
                             link_id,
                             temporary: TemporaryListItemTemplate {
@@ -468,13 +727,14 @@ impl ListItemDbPostgres<'_> {
                             },
                         },
                         list_reference: None,
+                        suggestions: Some(suggestions),
                     },
                 })
             }
         }
     }
 
-    async fn update_by_id(
+    pub async fn update_by_id(
         tx: &mut PgTransaction<'_>,
         list_id: &Uuid,
         id: &Uuid,
@@ -593,23 +853,382 @@ impl ListItemDbPostgres<'_> {
         if let Some(checked) = update.checked {
             item.data.checked = checked;
         }
+        if let Some(quantity) = update.quantity.as_ref() {
+            item.data.quantity = quantity.copied();
+        }
+        if let Some(unit) = update.unit.as_ref() {
+            item.data.unit = unit.cloned();
+        }
+        if let Some(note) = update.note.as_ref() {
+            item.data.note = note.cloned();
+        }
 
         let row = sqlx::query(
             "
             UPDATE public.list_items
             SET checked = $2,
+                quantity = $3,
+                unit = $4,
+                note = $5,
                 ts_updated = NOW()
             WHERE id = $1
-            RETURNING ts_updated
+            RETURNING ts_updated, ts_checked
             ",
         )
         .bind(id)
         .bind(item.data.checked)
+        .bind(item.data.quantity)
+        .bind(&item.data.unit)
+        .bind(&item.data.note)
         .fetch_one(&mut **tx)
         .await?;
 
         item.ts_updated = row.get("ts_updated");
+        item.ts_checked = row.get("ts_checked");
 
         Ok(item)
     }
+
+    async fn apply(
+        tx: &mut PgTransaction<'_>,
+        list_id: &Uuid,
+        operation: ListItemBatchOperation,
+    ) -> Result<()> {
+        match operation {
+            ListItemBatchOperation::DeleteChecked => {
+                // Relying on SQL trigger to delete corresponding list item types
+                sqlx::query(
+                    "
+                    DELETE FROM public.list_items
+                    WHERE list_id = $1 AND checked
+                    ",
+                )
+                .bind(list_id)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            ListItemBatchOperation::SetChecked { checked } => {
+                sqlx::query(
+                    "
+                    UPDATE public.list_items
+                    SET checked = $2,
+                        ts_updated = NOW()
+                    WHERE list_id = $1 AND checked <> $2
+                    ",
+                )
+                .bind(list_id)
+                .bind(checked)
+                .execute(&mut **tx)
+                .await?;
+            }
+
+            ListItemBatchOperation::Delete { mut ids } => {
+                // Repeated ids would otherwise count as missing items
+                ids.sort();
+                ids.dedup();
+
+                // Relying on SQL trigger to delete corresponding list item types
+                if sqlx::query(
+                    "
+                    DELETE FROM public.list_items
+                    WHERE list_id = $1 AND id = ANY($2)
+                    ",
+                )
+                .bind(list_id)
+                .bind(&ids)
+                .execute(&mut **tx)
+                .await?
+                .rows_affected()
+                    != ids.len() as u64
+                {
+                    return Err((DbError::NotFound).into());
+                }
+            }
+
+            ListItemBatchOperation::Update { id, data } => {
+                Self::update_by_id(tx, list_id, &id, *data).await?;
+            }
+
+            ListItemBatchOperation::Move {
+                mut ids,
+                list_id: target_list_id,
+            } => {
+                ids.sort();
+                ids.dedup();
+
+                if sqlx::query(
+                    "
+                    SELECT id
+                    FROM public.lists
+                    WHERE id = $1
+                    ",
+                )
+                .bind(target_list_id)
+                .fetch_optional(&mut **tx)
+                .await?
+                .is_none()
+                {
+                    return Err((DbError::NotFound).into());
+                }
+
+                // The list item references its link row, which belongs to no list itself
+                if sqlx::query(
+                    "
+                    UPDATE public.list_items
+                    SET list_id = $3,
+                        ts_updated = NOW()
+                    WHERE list_id = $1 AND id = ANY($2)
+                    ",
+                )
+                .bind(list_id)
+                .bind(&ids)
+                .bind(target_list_id)
+                .execute(&mut **tx)
+                .await?
+                .rows_affected()
+                    != ids.len() as u64
+                {
+                    return Err((DbError::NotFound).into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn convert_to_product(
+        tx: &mut PgTransaction<'_>,
+        list_id: &Uuid,
+        id: &Uuid,
+        conversion: ListItemConversion,
+    ) -> Result<ListItem> {
+        let item = Self::get_by_id(&mut **tx, list_id, id).await?;
+
+        let (temporary_link_id, name) = match item.data.kind {
+            ListItemKindTemplate::Temporary { link_id, temporary } => {
+                (link_id, temporary.data.name)
+            }
+
+            // Only temporary list items can be converted
+            _ => return Err((DbError::InvalidOperation).into()),
+        };
+
+        let product_id = match conversion.product {
+            Some(product) => {
+                if sqlx::query(
+                    "
+                    SELECT id
+                    FROM public.products
+                    WHERE id = $1
+                    ",
+                )
+                .bind(product.id)
+                .fetch_optional(&mut **tx)
+                .await?
+                .is_none()
+                {
+                    return Err((DbError::NotFound).into());
+                }
+
+                product.id
+            }
+            None => Self::get_or_create_product(tx, &name).await?,
+        };
+
+        let link_id = Uuid::new_v4();
+        sqlx::query(
+            "
+            INSERT INTO public.product_list_items (id, product_id)
+            VALUES ($1, $2)
+            ",
+        )
+        .bind(link_id)
+        .bind(product_id)
+        .execute(&mut **tx)
+        .await?;
+
+        // Swap both references at once to satisfy the single reference constraint
+        sqlx::query(
+            "
+            UPDATE public.list_items
+            SET temporary_list_item_id = NULL,
+                product_list_item_id = $2,
+                ts_updated = NOW()
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .bind(link_id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            "
+            DELETE FROM public.temporary_list_items
+            WHERE id = $1
+            ",
+        )
+        .bind(temporary_link_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Self::get_by_id(&mut **tx, list_id, id).await
+    }
+
+    async fn check_list_exists(tx: &mut PgTransaction<'_>, list_id: &Uuid) -> Result<()> {
+        if sqlx::query(
+            "
+            SELECT id
+            FROM public.lists
+            WHERE id = $1
+            ",
+        )
+        .bind(list_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .is_none()
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        Ok(())
+    }
+
+    async fn get_or_create_product(tx: &mut PgTransaction<'_>, name: &str) -> Result<Uuid> {
+        let existing = sqlx::query(
+            "
+            SELECT id
+            FROM public.products
+            WHERE normalize_text(name) = normalize_text($1)
+            ORDER BY ts_created
+            LIMIT 1
+            ",
+        )
+        .bind(name)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        if let Some(existing) = existing {
+            return Ok(existing.get("id"));
+        }
+
+        let product_id = Uuid::new_v4();
+        sqlx::query(
+            "
+            INSERT INTO public.products (id, name)
+            VALUES ($1, $2)
+            ",
+        )
+        .bind(product_id)
+        .bind(name)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(product_id)
+    }
+
+    async fn resolve_temporaries(
+        tx: &mut PgTransaction<'_>,
+        list_id: &Uuid,
+        resolution: ListItemResolution,
+    ) -> Result<Vec<ListItem>> {
+        Self::check_list_exists(tx, list_id).await?;
+
+        let threshold = resolution.threshold.unwrap_or(RESOLUTION_THRESHOLD);
+
+        let temporaries = sqlx::query(
+            "
+            SELECT
+                list_items.id,
+                temporary_list_items.name
+            FROM public.list_items
+                INNER JOIN public.temporary_list_items
+                    ON list_items.temporary_list_item_id = temporary_list_items.id
+            WHERE list_items.list_id = $1
+            ORDER BY list_items.id
+            ",
+        )
+        .bind(list_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let mut resolved = Vec::new();
+
+        for temporary in temporaries {
+            let name: String = temporary.get("name");
+            let best = ProductDbPostgres::get_similar(tx, &name, threshold, 1)
+                .await?
+                .pop();
+
+            if let Some(product) = best {
+                let conversion = ListItemConversion {
+                    product: Some(product),
+                };
+
+                resolved.push(
+                    Self::convert_to_product(tx, list_id, &temporary.get("id"), conversion).await?,
+                );
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    async fn quick_add(
+        tx: &mut PgTransaction<'_>,
+        list_id: &Uuid,
+        quick_add: ListItemQuickAdd,
+    ) -> Result<Vec<ListItemInterpretation>> {
+        Self::check_list_exists(tx, list_id).await?;
+
+        let threshold = quick_add.threshold.unwrap_or(RESOLUTION_THRESHOLD);
+        let mut interpreted = Vec::new();
+
+        for fragment in quick_add::parse(&quick_add.text) {
+            let best = ProductDbPostgres::get_similar(tx, &fragment.name, threshold, 1)
+                .await?
+                .pop();
+
+            let kind = match &best {
+                Some(product) => ListItemKindTemplate::Product {
+                    link_id: (),
+                    product: ProductReference {
+                        id: product.id,
+                        ..Default::default()
+                    },
+                },
+                None => ListItemKindTemplate::Temporary {
+                    link_id: (),
+                    temporary: TemporaryListItemTemplate {
+                        data: TemporaryListItemDataTemplate {
+                            name: fragment.name.clone(),
+                        },
+                    },
+                },
+            };
+
+            let create = ListItemCreate {
+                checked: false,
+                quantity: fragment.quantity,
+                unit: fragment.unit.clone(),
+                note: None,
+                kind,
+                list_reference: None,
+                suggestions: None,
+            };
+            let mut item = Self::create(tx, list_id, create).await?;
+
+            // Report the matched product along with its similarity
+            if let (ListItemKindTemplate::Product { product, .. }, Some(best)) =
+                (&mut item.data.kind, best)
+            {
+                *product = best;
+            }
+
+            interpreted.push(ListItemInterpretation { fragment, item });
+        }
+
+        Ok(interpreted)
+    }
 }
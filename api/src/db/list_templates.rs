@@ -0,0 +1,633 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
+use uuid::Uuid;
+
+use crate::utilities::modifier::{Create, Modifier, Query, Update};
+
+use super::{
+    list_items::{
+        ListItem, ListItemCreate, ListItemDataTemplate, ListItemDbPostgres, ListItemKindTemplate,
+        TemporaryListItemDataTemplate, TemporaryListItemTemplate,
+    },
+    lists::{List, ListCreate, ListDbPostgres, ListItemOrder},
+    products::{ProductDataTemplate, ProductReference},
+    DbError,
+};
+
+#[trait_variant::make(Send)]
+pub trait ListTemplateDb {
+    async fn get_multiple(&mut self) -> Result<Vec<ListTemplate>>;
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<ListTemplate>;
+    async fn create_multiple(
+        &mut self,
+        items: Vec<ListTemplateCreate>,
+    ) -> Result<Vec<ListTemplate>>;
+    async fn update_by_id(&mut self, id: &Uuid, item: ListTemplateUpdate) -> Result<ListTemplate>;
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
+    async fn apply(
+        &mut self,
+        id: &Uuid,
+        application: ListTemplateApplication,
+    ) -> Result<Vec<ListItem>>;
+    async fn instantiate(
+        &mut self,
+        id: &Uuid,
+        instantiation: ListTemplateInstantiation,
+    ) -> Result<List>;
+}
+
+pub type ListTemplate = ListTemplateTemplate<Query>;
+pub type ListTemplateCreate = ListTemplateDataTemplate<Create>;
+pub type ListTemplateUpdate = ListTemplateDataTemplate<Update>;
+
+pub type ListTemplateItem = ListTemplateItemTemplate<Query>;
+pub type ListTemplateItemCreate = ListTemplateItemTemplate<Create>;
+pub type ListTemplateItemUpdate = ListTemplateItemTemplate<Update>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ListTemplateTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<ListTemplateDataTemplate<M>>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ListTemplateDataTemplate<M: Modifier> {
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub name: M::Data<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub items: M::Data<Vec<ListTemplateItemTemplate<M>>>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ListTemplateItemTemplate<M: Modifier> {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub quantity: M::Nullable<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub unit: M::Nullable<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub note: M::Nullable<String>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub kind: M::Data<ListTemplateItemKindTemplate<M>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ListTemplateItemKindTemplate<M: Modifier> {
+    Product {
+        #[serde(flatten)]
+        product: M::Data<ProductReference>,
+    },
+    Temporary {
+        #[serde(flatten)]
+        temporary: M::Data<TemporaryListItemTemplate<M>>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListTemplateApplication {
+    pub list_id: Uuid,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ListTemplateInstantiation {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl FromRow<'_, PgRow> for ListTemplate {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            ts_created: row.get("ts_created"),
+            ts_updated: row.get("ts_updated"),
+            data: ListTemplateDataTemplate {
+                name: row.get("name"),
+                items: Vec::new(),
+            },
+        })
+    }
+}
+
+impl FromRow<'_, PgRow> for ListTemplateItem {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            quantity: row.get("item_quantity"),
+            unit: row.get("item_unit"),
+            note: row.get("item_note"),
+            kind: {
+                if let Some(id) = row.get("product_id") {
+                    ListTemplateItemKindTemplate::Product {
+                        product: ProductReference {
+                            id,
+                            data: Some(ProductDataTemplate {
+                                name: Some(row.get("product_name")),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                    }
+                } else {
+                    ListTemplateItemKindTemplate::Temporary {
+                        temporary: TemporaryListItemTemplate {
+                            data: TemporaryListItemDataTemplate {
+                                name: row.get("temporary_name"),
+                            },
+                        },
+                    }
+                }
+            },
+        })
+    }
+}
+
+impl ListTemplate {
+    async fn try_items_from_stream(
+        rows: &mut (impl Stream<Item = Result<PgRow, sqlx::Error>> + Unpin),
+    ) -> Result<Vec<Self>> {
+        let mut items: Vec<ListTemplate> = Vec::new();
+
+        while let Some(row) = rows.try_next().await? {
+            if items.last().map(|item| item.id) != Some(row.get("id")) {
+                items.push(ListTemplate::from_row(&row)?);
+            }
+
+            if row.get::<Option<Uuid>, _>("item_id").is_some() {
+                if let Some(item) = items.last_mut() {
+                    item.data.items.push(ListTemplateItem::from_row(&row)?);
+                }
+            }
+        }
+
+        Ok(items)
+    }
+}
+
+impl From<ListTemplateItem> for ListItemCreate {
+    fn from(item: ListTemplateItem) -> Self {
+        ListItemDataTemplate {
+            checked: false,
+            quantity: item.quantity,
+            unit: item.unit,
+            note: item.note,
+            kind: match item.kind {
+                ListTemplateItemKindTemplate::Product { product } => {
+                    ListItemKindTemplate::Product {
+                        link_id: (),
+                        product,
+                    }
+                }
+                ListTemplateItemKindTemplate::Temporary { temporary } => {
+                    ListItemKindTemplate::Temporary {
+                        link_id: (),
+                        temporary: TemporaryListItemTemplate {
+                            data: TemporaryListItemDataTemplate {
+                                name: temporary.data.name,
+                            },
+                        },
+                    }
+                }
+            },
+            list_reference: None,
+            suggestions: None,
+        }
+    }
+}
+
+impl ListTemplateItemUpdate {
+    // Template items are replaced as a whole, so every item must be complete
+    fn into_create(self) -> Result<ListTemplateItemCreate, DbError> {
+        let missing =
+            |field: &str| DbError::Validation(format!("template item is missing {}", field));
+
+        Ok(ListTemplateItemCreate {
+            quantity: self.quantity.as_ref().flatten().copied(),
+            unit: self.unit.as_ref().flatten().cloned(),
+            note: self.note.as_ref().flatten().cloned(),
+            kind: match self.kind.ok_or_else(|| missing("kind"))? {
+                ListTemplateItemKindTemplate::Product { product } => {
+                    ListTemplateItemKindTemplate::Product {
+                        product: product.ok_or_else(|| missing("product"))?,
+                    }
+                }
+                ListTemplateItemKindTemplate::Temporary { temporary } => {
+                    ListTemplateItemKindTemplate::Temporary {
+                        temporary: TemporaryListItemTemplate {
+                            data: TemporaryListItemDataTemplate {
+                                name: temporary
+                                    .and_then(|temporary| temporary.data)
+                                    .and_then(|data| data.name)
+                                    .ok_or_else(|| missing("name"))?,
+                            },
+                        },
+                    }
+                }
+            },
+        })
+    }
+}
+
+pub struct ListTemplateDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ListTemplateDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ListTemplateDb for ListTemplateDbPostgres<'_> {
+    async fn get_multiple(&mut self) -> Result<Vec<ListTemplate>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_multiple(&mut *conn, None).await
+    }
+
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<ListTemplate> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut *conn, id).await
+    }
+
+    async fn create_multiple(
+        &mut self,
+        items: Vec<ListTemplateCreate>,
+    ) -> Result<Vec<ListTemplate>> {
+        let mut tx = self.pool.begin().await?;
+        let mut created = Vec::new();
+
+        for item in items {
+            match Self::create(&mut tx, item).await {
+                Ok(item) => created.push(item),
+                Err(error) => {
+                    tx.rollback().await?;
+                    return Err(error);
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn update_by_id(&mut self, id: &Uuid, item: ListTemplateUpdate) -> Result<ListTemplate> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = match Self::update_by_id(&mut tx, id, item).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        // Relying on cascaded delete of corresponding template items
+        if sqlx::query(
+            "
+            DELETE FROM public.list_templates
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        Ok(())
+    }
+
+    async fn apply(
+        &mut self,
+        id: &Uuid,
+        application: ListTemplateApplication,
+    ) -> Result<Vec<ListItem>> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = match Self::apply(&mut tx, id, &application.list_id).await {
+            Ok(items) => items,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn instantiate(
+        &mut self,
+        id: &Uuid,
+        instantiation: ListTemplateInstantiation,
+    ) -> Result<List> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = match Self::instantiate(&mut tx, id, instantiation).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+}
+
+impl ListTemplateDbPostgres<'_> {
+    async fn get_multiple<'c, E>(executor: E, id: Option<&Uuid>) -> Result<Vec<ListTemplate>>
+    where
+        E: PgExecutor<'c>,
+    {
+        let mut stream = sqlx::query(
+            "
+            SELECT
+                list_templates.id,
+                list_templates.ts_created,
+                list_templates.ts_updated,
+                list_templates.name,
+                list_template_items.id AS item_id,
+                list_template_items.quantity AS item_quantity,
+                list_template_items.unit AS item_unit,
+                list_template_items.note AS item_note,
+                list_template_items.temporary_name,
+                products.id AS product_id,
+                products.name AS product_name
+
+            FROM public.list_templates
+                LEFT JOIN public.list_template_items
+                    ON list_templates.id = list_template_items.list_template_id
+                LEFT JOIN public.products
+                    ON list_template_items.product_id = products.id
+
+            WHERE list_templates.id = $1 OR $1 IS NULL
+            ORDER BY
+                list_templates.name,
+                list_templates.id,
+                list_template_items.sequence_number
+            ",
+        )
+        .bind(id)
+        .fetch(executor);
+
+        ListTemplate::try_items_from_stream(&mut stream).await
+    }
+
+    async fn get_by_id<'c, E>(executor: E, id: &Uuid) -> Result<ListTemplate>
+    where
+        E: PgExecutor<'c>,
+    {
+        match Self::get_multiple(executor, Some(id)).await?.pop() {
+            Some(item) => Ok(item),
+            None => Err((DbError::NotFound).into()),
+        }
+    }
+
+    async fn create(
+        tx: &mut PgTransaction<'_>,
+        create: ListTemplateCreate,
+    ) -> Result<ListTemplate> {
+        let item_id = Uuid::new_v4();
+        sqlx::query(
+            "
+            INSERT INTO public.list_templates (id, name)
+            VALUES ($1, $2)
+            ",
+        )
+        .bind(item_id)
+        .bind(create.name)
+        .execute(&mut **tx)
+        .await?;
+
+        for (seq, item) in create.items.into_iter().enumerate() {
+            Self::create_item(tx, &item_id, seq as i32, item).await?;
+        }
+
+        Self::get_by_id(&mut **tx, &item_id).await
+    }
+
+    async fn create_item(
+        tx: &mut PgTransaction<'_>,
+        template_id: &Uuid,
+        seq: i32,
+        create: ListTemplateItemCreate,
+    ) -> Result<()> {
+        let (product_id, temporary_name) = match create.kind {
+            ListTemplateItemKindTemplate::Product { product } => (Some(product.id), None),
+            ListTemplateItemKindTemplate::Temporary { temporary } => {
+                (None, Some(temporary.data.name))
+            }
+        };
+
+        sqlx::query(
+            "
+            INSERT INTO public.list_template_items (
+                id, list_template_id, product_id, temporary_name,
+                quantity, unit, note, sequence_number
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(template_id)
+        .bind(product_id)
+        .bind(temporary_name)
+        .bind(create.quantity)
+        .bind(create.unit)
+        .bind(create.note)
+        .bind(seq)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_by_id(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        update: ListTemplateUpdate,
+    ) -> Result<ListTemplate> {
+        let mut item = Self::get_by_id(&mut **tx, id).await?;
+
+        if let Some(name) = update.name {
+            item.data.name = name;
+        }
+
+        sqlx::query(
+            "
+            UPDATE public.list_templates
+            SET name = $2,
+                ts_updated = NOW()
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .bind(item.data.name.clone())
+        .execute(&mut **tx)
+        .await?;
+
+        if let Some(items) = update.items {
+            sqlx::query(
+                "
+                DELETE FROM public.list_template_items
+                WHERE list_template_id = $1
+                ",
+            )
+            .bind(id)
+            .execute(&mut **tx)
+            .await?;
+
+            let items = items
+                .into_iter()
+                .map(ListTemplateItemUpdate::into_create)
+                .collect::<Result<Vec<_>, _>>()?;
+            for (seq, item) in items.into_iter().enumerate() {
+                Self::create_item(tx, id, seq as i32, item).await?;
+            }
+        }
+
+        Self::get_by_id(&mut **tx, id).await
+    }
+
+    async fn apply(tx: &mut PgTransaction<'_>, id: &Uuid, list_id: &Uuid) -> Result<Vec<ListItem>> {
+        let template = Self::get_by_id(&mut **tx, id).await?;
+
+        if sqlx::query(
+            "
+            SELECT id
+            FROM public.lists
+            WHERE id = $1
+            ",
+        )
+        .bind(list_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .is_none()
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        let mut created = Vec::new();
+
+        for item in template.data.items {
+            if Self::is_present(tx, list_id, &item.kind).await? {
+                continue;
+            }
+
+            created.push(ListItemDbPostgres::create(tx, list_id, item.into()).await?);
+        }
+
+        Ok(created)
+    }
+
+    async fn is_present(
+        tx: &mut PgTransaction<'_>,
+        list_id: &Uuid,
+        kind: &ListTemplateItemKindTemplate<Query>,
+    ) -> Result<bool> {
+        let present = match kind {
+            // Products may also be on the list by way of an ingredient
+            ListTemplateItemKindTemplate::Product { product } => sqlx::query(
+                "
+                SELECT list_items.id
+                FROM public.list_items
+                    LEFT JOIN public.product_list_items
+                        ON list_items.product_list_item_id = product_list_items.id
+                    LEFT JOIN public.ingredient_list_items
+                        ON list_items.ingredient_list_item_id = ingredient_list_items.id
+                    LEFT JOIN public.ingredients
+                        ON ingredient_list_items.ingredient_id = ingredients.id
+                WHERE
+                    list_items.list_id = $1 AND (
+                        product_list_items.product_id = $2 OR
+                        ingredients.product_id = $2
+                    )
+                LIMIT 1
+                ",
+            )
+            .bind(list_id)
+            .bind(product.id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .is_some(),
+
+            // Temporary items match any item by name
+            ListTemplateItemKindTemplate::Temporary { temporary } => sqlx::query(
+                "
+                SELECT list_items.id
+                FROM public.list_items
+                    LEFT JOIN public.temporary_list_items
+                        ON list_items.temporary_list_item_id = temporary_list_items.id
+                    LEFT JOIN public.product_list_items
+                        ON list_items.product_list_item_id = product_list_items.id
+                    LEFT JOIN public.products
+                        ON product_list_items.product_id = products.id
+                WHERE
+                    list_items.list_id = $1 AND (
+                        normalize_text(temporary_list_items.name) = normalize_text($2) OR
+                        normalize_text(products.name) = normalize_text($2)
+                    )
+                LIMIT 1
+                ",
+            )
+            .bind(list_id)
+            .bind(&temporary.data.name)
+            .fetch_optional(&mut **tx)
+            .await?
+            .is_some(),
+        };
+
+        Ok(present)
+    }
+
+    async fn instantiate(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        instantiation: ListTemplateInstantiation,
+    ) -> Result<List> {
+        let template = Self::get_by_id(&mut **tx, id).await?;
+
+        let list = ListDbPostgres::create(
+            tx,
+            ListCreate {
+                name: instantiation.name.unwrap_or(template.data.name),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+        for item in template.data.items {
+            ListItemDbPostgres::create(tx, &list.id, item.into()).await?;
+        }
+
+        ListDbPostgres::get_by_id(&mut **tx, &list.id, ListItemOrder::default()).await
+    }
+}
@@ -7,7 +7,10 @@ use serde::{Deserialize, Serialize};
 use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
 use uuid::Uuid;
 
-use crate::utilities::modifier::{Create, Modifier, Query, Reference, Update};
+use crate::utilities::{
+    merge::{plan_merge, MergeItem, MergeStep},
+    modifier::{Create, Modifier, Query, Reference, Update},
+};
 
 use super::{
     ingredient_collections::IngredientCollectionReference,
@@ -22,11 +25,13 @@ use super::{
 
 #[trait_variant::make(Send)]
 pub trait ListDb {
-    async fn get_multiple(&mut self) -> Result<Vec<List>>;
-    async fn get_by_id(&mut self, id: &Uuid) -> Result<List>;
+    async fn get_multiple(&mut self, params: SearchParams) -> Result<Vec<List>>;
+    async fn get_by_id(&mut self, id: &Uuid, params: ListParams) -> Result<List>;
     async fn create_multiple(&mut self, items: Vec<ListCreate>) -> Result<Vec<List>>;
     async fn update_by_id(&mut self, id: &Uuid, item: ListUpdate) -> Result<List>;
     async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
+    async fn duplicate(&mut self, id: &Uuid, duplication: ListDuplication) -> Result<List>;
+    async fn merge(&mut self, id: &Uuid, merge: ListMerge) -> Result<List>;
 }
 
 pub type List = ListTemplate<Query>;
@@ -49,6 +54,9 @@ pub struct ListTemplate<M: Modifier> {
 pub struct ListDataTemplate<M: Modifier> {
     #[serde(skip_serializing_if = "M::skip_data")]
     pub name: M::Data<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub archived: M::Data<bool>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "M::skip_data")]
     pub item_refs: M::Data<ListItemReferences<Reference>>,
@@ -61,6 +69,41 @@ pub struct ListItemReferences<M: Modifier> {
     pub items: M::Data<Vec<ListItemReference>>,
 }
 
+#[derive(Default, Debug, Deserialize)]
+pub struct SearchParams {
+    #[serde(default)]
+    pub archived: bool,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ListDuplication {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListMerge {
+    pub list_id: Uuid,
+    // The other list is left as it is, unless it should be archived once merged
+    #[serde(default)]
+    pub archive_source: bool,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct ListParams {
+    #[serde(default)]
+    pub order: ListItemOrder,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListItemOrder {
+    #[default]
+    Name,
+    // Learned from the sequence in which items were checked on past trips
+    Store,
+}
+
 impl FromRow<'_, PgRow> for List {
     fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
         Ok(Self {
@@ -69,6 +112,7 @@ impl FromRow<'_, PgRow> for List {
             ts_updated: row.get("ts_updated"),
             data: ListDataTemplate {
                 name: row.get("name"),
+                archived: row.get("archived"),
                 item_refs: ListItemReferences { items: None },
             },
         })
@@ -113,6 +157,7 @@ impl List {
             ts_updated: first.get("ts_updated"),
             data: ListDataTemplate {
                 name: first.get("name"),
+                archived: first.get("archived"),
                 item_refs: if summary {
                     ListItemReferences { items: None }
                 } else {
@@ -151,6 +196,9 @@ impl List {
             id: first.get("item_id"),
             data: Some(ListItemDataTemplate {
                 checked: Some(first.get("item_checked")),
+                quantity: first.get("item_quantity"),
+                unit: first.get("item_unit"),
+                note: first.get("item_note"),
                 kind: Some({
                     if let Some(id) = first.get("ingredient_list_item_id") {
                         ListItemKindTemplate::Ingredient {
@@ -158,6 +206,8 @@ impl List {
                             ingredient: Some(IngredientReference {
                                 id: first.get("ingredient_id"),
                                 data: Some(IngredientDataTemplate {
+                                    quantity: first.get("ingredient_quantity"),
+                                    unit: first.get("ingredient_unit"),
                                     product: Some(ProductReference {
                                         id: first.get("ingredient_product_id"),
                                         data: Some(ProductDataTemplate {
@@ -218,7 +268,7 @@ impl<'a> ListDbPostgres<'a> {
 }
 
 impl ListDb for ListDbPostgres<'_> {
-    async fn get_multiple(&mut self) -> Result<Vec<List>> {
+    async fn get_multiple(&mut self, params: SearchParams) -> Result<Vec<List>> {
         let mut conn = self.pool.acquire().await?;
         let stream = sqlx::query(
             "
@@ -226,22 +276,25 @@ impl ListDb for ListDbPostgres<'_> {
                 lists.id,
                 lists.ts_created,
                 lists.ts_updated,
-                lists.name
+                lists.name,
+                lists.archived
 
             FROM public.lists
 
+            WHERE lists.archived = $1
             ORDER BY lists.name
             ",
         )
+        .bind(params.archived)
         .fetch(&mut *conn);
 
         List::collect_lists(stream, true).await
     }
 
-    async fn get_by_id(&mut self, id: &Uuid) -> Result<List> {
+    async fn get_by_id(&mut self, id: &Uuid, params: ListParams) -> Result<List> {
         let mut conn = self.pool.acquire().await?;
 
-        Self::get_by_id(&mut *conn, id).await
+        Self::get_by_id(&mut *conn, id, params.order).await
     }
 
     async fn create_multiple(&mut self, items: Vec<ListCreate>) -> Result<Vec<List>> {
@@ -300,10 +353,42 @@ impl ListDb for ListDbPostgres<'_> {
 
         Ok(())
     }
+
+    async fn duplicate(&mut self, id: &Uuid, duplication: ListDuplication) -> Result<List> {
+        let mut tx = self.pool.begin().await?;
+
+        let duplicated = match Self::duplicate(&mut tx, id, duplication).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(duplicated)
+    }
+
+    async fn merge(&mut self, id: &Uuid, merge: ListMerge) -> Result<List> {
+        let mut tx = self.pool.begin().await?;
+
+        let merged = match Self::merge(&mut tx, id, merge).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(merged)
+    }
 }
 
 impl ListDbPostgres<'_> {
-    async fn get_by_id<'c, E>(executor: E, id: &Uuid) -> Result<List>
+    pub async fn get_by_id<'c, E>(executor: E, id: &Uuid, order: ListItemOrder) -> Result<List>
     where
         E: PgExecutor<'c>,
     {
@@ -314,12 +399,18 @@ impl ListDbPostgres<'_> {
                 lists.ts_created,
                 lists.ts_updated,
                 lists.name,
+                lists.archived,
                 list_items.id AS item_id,
                 list_items.ts_created AS item_ts_created,
                 list_items.ts_updated AS item_ts_updated,
                 list_items.checked AS item_checked,
+                list_items.quantity AS item_quantity,
+                list_items.unit AS item_unit,
+                list_items.note AS item_note,
                 ingredient_list_items.id AS ingredient_list_item_id,
                 ingredients.id AS ingredient_id,
+                ingredients.quantity AS ingredient_quantity,
+                ingredients.unit AS ingredient_unit,
                 ingredient_products.id AS ingredient_product_id,
                 ingredient_products.name AS ingredient_product_name,
                 ingredients.ingredient_collection_id AS ingredient_collection_id,
@@ -348,15 +439,20 @@ impl ListDbPostgres<'_> {
                 LEFT JOIN public.temporary_list_items
                     ON list_items.temporary_list_item_id = temporary_list_items.id
 
+                LEFT JOIN public.product_positions
+                    ON COALESCE(products.id, ingredient_products.id) = product_positions.product_id
+
             WHERE lists.id = $1
             ORDER BY
                 lists.name,
                 lists.id,
+                CASE WHEN $2 THEN product_positions.position END NULLS LAST,
                 COALESCE(products.name, temporary_list_items.name),
                 list_items.id
             ",
         )
         .bind(id)
+        .bind(order == ListItemOrder::Store)
         .fetch(executor);
 
         match List::collect_lists(stream, false).await?.pop() {
@@ -365,17 +461,18 @@ impl ListDbPostgres<'_> {
         }
     }
 
-    async fn create(tx: &mut PgTransaction<'_>, create: ListCreate) -> Result<List> {
+    pub async fn create(tx: &mut PgTransaction<'_>, create: ListCreate) -> Result<List> {
         let item_id = Uuid::new_v4();
         let item: List = sqlx::query_as(
             "
-            INSERT INTO public.lists (id, name)
-            VALUES ($1, $2)
-            RETURNING id, ts_created, ts_updated, name
+            INSERT INTO public.lists (id, name, archived)
+            VALUES ($1, $2, $3)
+            RETURNING id, ts_created, ts_updated, name, archived
             ",
         )
         .bind(item_id)
         .bind(create.name)
+        .bind(create.archived)
         .fetch_one(&mut **tx)
         .await?;
 
@@ -387,16 +484,20 @@ impl ListDbPostgres<'_> {
         id: &Uuid,
         update: ListUpdate,
     ) -> Result<List> {
-        let mut item = Self::get_by_id(&mut **tx, id).await?;
+        let mut item = Self::get_by_id(&mut **tx, id, ListItemOrder::default()).await?;
 
         if let Some(name) = update.name {
             item.data.name = name;
         }
+        if let Some(archived) = update.archived {
+            item.data.archived = archived;
+        }
 
         let row = sqlx::query(
             "
             UPDATE public.lists
             SET name = $2,
+                archived = $3,
                 ts_updated = NOW()
             WHERE id = $1
             RETURNING ts_updated
@@ -404,6 +505,7 @@ impl ListDbPostgres<'_> {
         )
         .bind(id)
         .bind(item.data.name.clone())
+        .bind(item.data.archived)
         .fetch_one(&mut **tx)
         .await?;
 
@@ -411,4 +513,238 @@ impl ListDbPostgres<'_> {
 
         Ok(item)
     }
+
+    async fn duplicate(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        duplication: ListDuplication,
+    ) -> Result<List> {
+        let source = Self::get_by_id(&mut **tx, id, ListItemOrder::default()).await?;
+
+        let list = Self::create(
+            tx,
+            ListCreate {
+                name: duplication
+                    .name
+                    .unwrap_or_else(|| format!("{} (copy)", source.data.name)),
+                archived: false,
+                item_refs: Default::default(),
+            },
+        )
+        .await?;
+
+        let items = sqlx::query(
+            "
+            SELECT id
+            FROM public.list_items
+            WHERE list_id = $1
+            ORDER BY ts_created, id
+            ",
+        )
+        .bind(id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        for item in items {
+            Self::copy_item(tx, &item.get("id"), &list.id).await?;
+        }
+
+        Self::get_by_id(&mut **tx, &list.id, ListItemOrder::default()).await
+    }
+
+    // Copies the item to the list, every list item type has its own link row, which cannot be
+    // shared
+    async fn copy_item(tx: &mut PgTransaction<'_>, id: &Uuid, list_id: &Uuid) -> Result<Uuid> {
+        let item = sqlx::query(
+            "
+            SELECT
+                ingredient_list_item_id,
+                product_list_item_id,
+                temporary_list_item_id
+            FROM public.list_items
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let ingredient_link_id: Option<Uuid> = item.get("ingredient_list_item_id");
+        let product_link_id: Option<Uuid> = item.get("product_list_item_id");
+        let temporary_link_id: Option<Uuid> = item.get("temporary_list_item_id");
+
+        let (query, link_id) = if let Some(link_id) = ingredient_link_id {
+            (
+                "
+                INSERT INTO public.ingredient_list_items (id, ingredient_id)
+                SELECT $1, ingredient_id
+                FROM public.ingredient_list_items
+                WHERE id = $2
+                ",
+                link_id,
+            )
+        } else if let Some(link_id) = product_link_id {
+            (
+                "
+                INSERT INTO public.product_list_items (id, product_id)
+                SELECT $1, product_id
+                FROM public.product_list_items
+                WHERE id = $2
+                ",
+                link_id,
+            )
+        } else if let Some(link_id) = temporary_link_id {
+            (
+                "
+                INSERT INTO public.temporary_list_items (id, name)
+                SELECT $1, name
+                FROM public.temporary_list_items
+                WHERE id = $2
+                ",
+                link_id,
+            )
+        } else {
+            return Err((DbError::InvalidOperation).into());
+        };
+
+        let new_link_id = Uuid::new_v4();
+        sqlx::query(query)
+            .bind(new_link_id)
+            .bind(link_id)
+            .execute(&mut **tx)
+            .await?;
+
+        let new_id = Uuid::new_v4();
+        sqlx::query(
+            "
+            INSERT INTO public.list_items (
+                id, list_id, checked, quantity, unit, note,
+                ingredient_list_item_id, product_list_item_id, temporary_list_item_id
+            )
+            SELECT $1, $2, checked, quantity, unit, note, $3, $4, $5
+            FROM public.list_items
+            WHERE id = $6
+            ",
+        )
+        .bind(new_id)
+        .bind(list_id)
+        .bind(ingredient_link_id.map(|_| new_link_id))
+        .bind(product_link_id.map(|_| new_link_id))
+        .bind(temporary_link_id.map(|_| new_link_id))
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(new_id)
+    }
+
+    async fn merge(tx: &mut PgTransaction<'_>, id: &Uuid, merge: ListMerge) -> Result<List> {
+        // Merging a list into itself would double its items
+        if *id == merge.list_id {
+            return Err((DbError::InvalidOperation).into());
+        }
+
+        let rows = sqlx::query(
+            "
+            SELECT
+                list_items.id,
+                lists.id AS list_id,
+                list_items.checked,
+                list_items.quantity,
+                list_items.unit,
+                COALESCE(product_list_items.product_id, ingredients.product_id) AS product_id
+            FROM public.lists
+                LEFT JOIN public.list_items
+                    ON lists.id = list_items.list_id
+                LEFT JOIN public.ingredient_list_items
+                    ON list_items.ingredient_list_item_id = ingredient_list_items.id
+                LEFT JOIN public.ingredients
+                    ON ingredient_list_items.ingredient_id = ingredients.id
+                LEFT JOIN public.product_list_items
+                    ON list_items.product_list_item_id = product_list_items.id
+            WHERE lists.id = ANY($1)
+            ORDER BY lists.id = $2 DESC, list_items.ts_created, list_items.id
+            ",
+        )
+        .bind(vec![*id, merge.list_id])
+        .bind(id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        // Both lists must exist, even when empty
+        for list_id in [id, &merge.list_id] {
+            if !rows
+                .iter()
+                .any(|row| row.get::<Uuid, _>("list_id") == *list_id)
+            {
+                return Err((DbError::NotFound).into());
+            }
+        }
+
+        let mut target = Vec::new();
+        let mut other = Vec::new();
+        for row in rows {
+            let item_id: Option<Uuid> = row.get("id");
+            let Some(item_id) = item_id else { continue };
+
+            let item = MergeItem {
+                id: item_id,
+                product_id: row.get("product_id"),
+                checked: row.get("checked"),
+                quantity: row.get("quantity"),
+                unit: row.get("unit"),
+            };
+            if row.get::<Uuid, _>("list_id") == *id {
+                target.push(item);
+            } else {
+                other.push(item);
+            }
+        }
+
+        for step in plan_merge(&target, &other) {
+            let (item_id, checked, quantity) = match step {
+                MergeStep::Update {
+                    id: item_id,
+                    checked,
+                    quantity,
+                } => (item_id, checked, quantity),
+                MergeStep::Copy {
+                    id: item_id,
+                    checked,
+                    quantity,
+                } => (Self::copy_item(tx, &item_id, id).await?, checked, quantity),
+            };
+
+            sqlx::query(
+                "
+                UPDATE public.list_items
+                SET checked = $2,
+                    quantity = $3,
+                    ts_updated = NOW()
+                WHERE id = $1 AND (checked, quantity) IS DISTINCT FROM ($2, $3)
+                ",
+            )
+            .bind(item_id)
+            .bind(checked)
+            .bind(quantity)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        if merge.archive_source {
+            sqlx::query(
+                "
+                UPDATE public.lists
+                SET archived = TRUE,
+                    ts_updated = NOW()
+                WHERE id = $1
+                ",
+            )
+            .bind(merge.list_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Self::get_by_id(&mut **tx, id, ListItemOrder::default()).await
+    }
 }
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{TryFutureExt, TryStreamExt};
+use pulldown_cmark::Options;
 use serde::{Deserialize, Serialize};
 use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
 use uuid::Uuid;
@@ -10,7 +11,7 @@ use crate::utilities::{
     modifier::{Create, Modifier, Query, Reference, Update},
 };
 
-use super::DbError;
+use super::{page_revisions::PageRevisionDbPostgres, DbError};
 
 #[trait_variant::make(Send)]
 pub trait MarkdownDb {
@@ -54,7 +55,7 @@ impl FromRow<'_, PgRow> for Markdown {
             ts_updated: row.get("ts_updated"),
             data: {
                 let markdown = row.get::<String, _>("markdown");
-                let html = markdown_to_html(&markdown);
+                let html = markdown_to_html(&markdown, Options::empty());
                 MarkdownDataTemplate {
                     markdown,
                     html: Some(html),
@@ -101,22 +102,11 @@ impl MarkdownDb for MarkdownDbPostgres<'_> {
         let mut created = Vec::new();
 
         for item in items {
-            match sqlx::query_as(
-                "
-                INSERT INTO public.markdown (id, markdown)
-                VALUES ($1, $2)
-                RETURNING id, ts_created, ts_updated, markdown
-                ",
-            )
-            .bind(Uuid::new_v4())
-            .bind(item.markdown)
-            .fetch_one(&mut *tx)
-            .await
-            {
+            match Self::create(&mut tx, item).await {
                 Ok(item) => created.push(item),
                 Err(error) => {
                     tx.commit().await?;
-                    return Err(error.into());
+                    return Err(error);
                 }
             };
         }
@@ -185,6 +175,20 @@ impl MarkdownDbPostgres<'_> {
         .await
     }
 
+    pub async fn create(tx: &mut PgTransaction<'_>, create: MarkdownCreate) -> Result<Markdown> {
+        Ok(sqlx::query_as(
+            "
+            INSERT INTO public.markdown (id, markdown)
+            VALUES ($1, $2)
+            RETURNING id, ts_created, ts_updated, markdown
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(create.markdown)
+        .fetch_one(&mut **tx)
+        .await?)
+    }
+
     async fn update_by_id(
         tx: &mut PgTransaction<'_>,
         id: &Uuid,
@@ -193,7 +197,7 @@ impl MarkdownDbPostgres<'_> {
         let mut item = Self::get_by_id(&mut **tx, id).await?;
 
         if let Some(markdown) = update.markdown {
-            item.data.html = Some(markdown_to_html(&markdown));
+            item.data.html = Some(markdown_to_html(&markdown, Options::empty()));
             item.data.markdown = markdown;
         }
 
@@ -213,6 +217,8 @@ impl MarkdownDbPostgres<'_> {
 
         item.ts_updated = row.get("ts_updated");
 
+        PageRevisionDbPostgres::record_with_markdown(tx, id).await?;
+
         Ok(item)
     }
 }
@@ -0,0 +1,817 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{TryFutureExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgConnection, PgPool, PgTransaction, Row};
+use uuid::Uuid;
+
+use crate::utilities::{
+    modifier::{Modifier, Query},
+    revisions::{self, Change},
+    tables::{self, Column},
+};
+
+use super::{
+    blocks::{BlockDbPostgres, Step},
+    pages::{Page, PageDbPostgres, PageUpdate},
+    tables::TableDbPostgres,
+    DbError,
+};
+
+// Revisions are recorded along with every change to a page and can not be changed themselves
+#[trait_variant::make(Send)]
+pub trait PageRevisionDb {
+    async fn get_multiple(&mut self, page_id: &Uuid) -> Result<Vec<PageRevision>>;
+    async fn get_by_id(&mut self, page_id: &Uuid, id: &Uuid) -> Result<PageRevision>;
+    async fn diff(
+        &mut self,
+        page_id: &Uuid,
+        id: &Uuid,
+        params: PageRevisionDiffParams,
+    ) -> Result<PageRevisionDiff>;
+    async fn restore(&mut self, page_id: &Uuid, id: &Uuid) -> Result<Page>;
+}
+
+pub type PageRevision = PageRevisionTemplate<Query>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct PageRevisionTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<PageRevisionData>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct PageRevisionData {
+    pub number: i32,
+    // Only included when fetching a single revision
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PageRevisionDiff {
+    pub from: PageRevision,
+    pub to: PageRevision,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageRevisionDiffParams {
+    pub to: Uuid,
+}
+
+impl FromRow<'_, PgRow> for PageRevision {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            ts_created: row.try_get("ts_created")?,
+            data: PageRevisionData {
+                number: row.try_get("number")?,
+                snapshot: match row.try_get::<Option<String>, _>("snapshot") {
+                    Ok(Some(snapshot)) => Some(
+                        serde_json::from_str(&snapshot)
+                            .map_err(|error| sqlx::Error::Decode(Box::new(error)))?,
+                    ),
+                    _ => None,
+                },
+            },
+        })
+    }
+}
+
+const RESTORE_REFERENCE_MESSAGE: &str = "revision refers to contents that no longer exist";
+const RESTORE_LISTED_MESSAGE: &str = "revision lacks ingredients that are on a list";
+
+// The parts of a snapshot that are restored besides the page itself
+#[derive(Deserialize)]
+struct Snapshot {
+    data: SnapshotData,
+}
+
+#[derive(Deserialize)]
+struct SnapshotData {
+    blocks: Vec<SnapshotBlock>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotBlock {
+    id: Uuid,
+    data: Option<SnapshotBlockData>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotBlockData {
+    kind: SnapshotBlockKind,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SnapshotBlockKind {
+    Markdown {
+        id: Uuid,
+        data: SnapshotMarkdown,
+    },
+    IngredientCollection {
+        id: Uuid,
+        data: SnapshotIngredientCollection,
+    },
+    Steps {
+        steps: Vec<Step>,
+    },
+    Image {
+        id: Uuid,
+    },
+    PageReference {
+        id: Uuid,
+    },
+    Checklist {
+        id: Uuid,
+        data: SnapshotChecklist,
+    },
+    Table {
+        id: Uuid,
+        data: SnapshotTable,
+    },
+}
+
+#[derive(Deserialize)]
+struct SnapshotMarkdown {
+    markdown: String,
+}
+
+#[derive(Deserialize)]
+struct SnapshotIngredientCollection {
+    ingredients: Vec<SnapshotIngredient>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotIngredient {
+    id: Uuid,
+    data: SnapshotIngredientData,
+}
+
+#[derive(Deserialize)]
+struct SnapshotIngredientData {
+    product: SnapshotProduct,
+    quantity: Option<f64>,
+    unit: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotProduct {
+    id: Uuid,
+}
+
+#[derive(Deserialize)]
+struct SnapshotChecklist {
+    items: Vec<SnapshotChecklistItem>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotChecklistItem {
+    id: Uuid,
+    data: SnapshotChecklistItemData,
+}
+
+#[derive(Deserialize)]
+struct SnapshotChecklistItemData {
+    text: String,
+    checked: bool,
+}
+
+#[derive(Deserialize)]
+struct SnapshotTable {
+    columns: Vec<Column>,
+    rows: Vec<tables::Row>,
+}
+
+pub struct PageRevisionDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> PageRevisionDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl PageRevisionDb for PageRevisionDbPostgres<'_> {
+    async fn get_multiple(&mut self, page_id: &Uuid) -> Result<Vec<PageRevision>> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query_as(
+            "
+            SELECT id, ts_created, number
+            FROM public.page_revisions
+            WHERE page_id = $1
+            ORDER BY number DESC
+            ",
+        )
+        .bind(page_id)
+        .fetch(&mut *conn)
+        .try_collect()
+        .map_err(|error| error.into())
+        .await
+    }
+
+    async fn get_by_id(&mut self, page_id: &Uuid, id: &Uuid) -> Result<PageRevision> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut conn, page_id, id).await
+    }
+
+    async fn diff(
+        &mut self,
+        page_id: &Uuid,
+        id: &Uuid,
+        params: PageRevisionDiffParams,
+    ) -> Result<PageRevisionDiff> {
+        let mut conn = self.pool.acquire().await?;
+
+        let mut from = Self::get_by_id(&mut conn, page_id, id).await?;
+        let mut to = Self::get_by_id(&mut conn, page_id, &params.to).await?;
+
+        let changes = match (&from.data.snapshot, &to.data.snapshot) {
+            (Some(before), Some(after)) => revisions::diff(before, after),
+            _ => Vec::new(),
+        };
+
+        // Both snapshots are summarized by the changes already
+        from.data.snapshot = None;
+        to.data.snapshot = None;
+
+        Ok(PageRevisionDiff { from, to, changes })
+    }
+
+    async fn restore(&mut self, page_id: &Uuid, id: &Uuid) -> Result<Page> {
+        let mut tx = self.pool.begin().await?;
+
+        let restored = match Self::restore(&mut tx, page_id, id).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(restored)
+    }
+}
+
+impl PageRevisionDbPostgres<'_> {
+    async fn get_by_id(conn: &mut PgConnection, page_id: &Uuid, id: &Uuid) -> Result<PageRevision> {
+        sqlx::query_as(
+            "
+            SELECT id, ts_created, number, snapshot::TEXT AS snapshot
+            FROM public.page_revisions
+            WHERE page_id = $1 AND id = $2
+            ",
+        )
+        .bind(page_id)
+        .bind(id)
+        .fetch_one(conn)
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::NotFound),
+            _ => error.into(),
+        })
+        .await
+    }
+
+    pub async fn record(conn: &mut PgConnection, page_id: &Uuid) -> Result<()> {
+        // Concurrent changes to the page would otherwise take the same revision number
+        sqlx::query(
+            "
+            SELECT id
+            FROM public.pages
+            WHERE id = $1
+            FOR UPDATE
+            ",
+        )
+        .bind(page_id)
+        .execute(&mut *conn)
+        .await?;
+
+        let page = PageDbPostgres::get_by_id(conn, page_id).await?;
+
+        sqlx::query(
+            "
+            INSERT INTO public.page_revisions (id, page_id, number, snapshot)
+            VALUES (
+                $1,
+                $2,
+                (
+                    SELECT COALESCE(MAX(number) + 1, 1)
+                    FROM public.page_revisions
+                    WHERE page_id = $2
+                ),
+                $3::JSONB
+            )
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .bind(page_id)
+        .bind(serde_json::to_string(&page)?)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    // Markdown is edited on its own, but is part of the pages that show it
+    pub async fn record_with_markdown(conn: &mut PgConnection, markdown_id: &Uuid) -> Result<()> {
+        let page_ids: Vec<Uuid> = sqlx::query(
+            "
+            SELECT DISTINCT page_blocks.page_id
+            FROM public.page_blocks
+                JOIN public.blocks
+                    ON page_blocks.block_id = blocks.id
+                JOIN public.markdown_blocks
+                    ON blocks.markdown_block_id = markdown_blocks.id
+            WHERE markdown_blocks.markdown_id = $1
+            ",
+        )
+        .bind(markdown_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.get("page_id"))
+        .collect();
+
+        Self::record_multiple(conn, &page_ids).await
+    }
+
+    // Ingredients are edited on their own, but are part of the pages that show their collection
+    pub async fn record_with_ingredient_collection(
+        conn: &mut PgConnection,
+        collection_id: &Uuid,
+    ) -> Result<()> {
+        let page_ids: Vec<Uuid> = sqlx::query(
+            "
+            SELECT DISTINCT page_blocks.page_id
+            FROM public.page_blocks
+                JOIN public.blocks
+                    ON page_blocks.block_id = blocks.id
+                JOIN public.ingredient_collection_blocks
+                    ON blocks.ingredient_collection_block_id = ingredient_collection_blocks.id
+            WHERE ingredient_collection_blocks.ingredient_collection_id = $1
+            ",
+        )
+        .bind(collection_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.get("page_id"))
+        .collect();
+
+        Self::record_multiple(conn, &page_ids).await
+    }
+
+    // Blocks are edited on their own, but are part of the pages that show them
+    pub async fn record_with_block(conn: &mut PgConnection, block_id: &Uuid) -> Result<()> {
+        let page_ids = Self::pages_with_block(conn, block_id).await?;
+
+        Self::record_multiple(conn, &page_ids).await
+    }
+
+    // Tables are edited on their own, but are part of the pages that show them
+    pub async fn record_with_table(conn: &mut PgConnection, table_id: &Uuid) -> Result<()> {
+        let page_ids: Vec<Uuid> = sqlx::query(
+            "
+            SELECT DISTINCT page_blocks.page_id
+            FROM public.page_blocks
+                JOIN public.blocks
+                    ON page_blocks.block_id = blocks.id
+                JOIN public.table_blocks
+                    ON blocks.table_block_id = table_blocks.id
+            WHERE table_blocks.table_id = $1
+            ",
+        )
+        .bind(table_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.get("page_id"))
+        .collect();
+
+        Self::record_multiple(conn, &page_ids).await
+    }
+
+    // Checklist items are edited on their own, but are part of the pages that show their checklist
+    pub async fn record_with_checklist(conn: &mut PgConnection, checklist_id: &Uuid) -> Result<()> {
+        let page_ids: Vec<Uuid> = sqlx::query(
+            "
+            SELECT DISTINCT page_blocks.page_id
+            FROM public.page_blocks
+                JOIN public.blocks
+                    ON page_blocks.block_id = blocks.id
+                JOIN public.checklist_blocks
+                    ON blocks.checklist_block_id = checklist_blocks.id
+            WHERE checklist_blocks.checklist_id = $1
+            ",
+        )
+        .bind(checklist_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.get("page_id"))
+        .collect();
+
+        Self::record_multiple(conn, &page_ids).await
+    }
+
+    pub async fn pages_with_block(conn: &mut PgConnection, block_id: &Uuid) -> Result<Vec<Uuid>> {
+        Ok(sqlx::query(
+            "
+            SELECT DISTINCT page_id
+            FROM public.page_blocks
+            WHERE block_id = $1
+            ",
+        )
+        .bind(block_id)
+        .fetch_all(&mut *conn)
+        .await?
+        .iter()
+        .map(|row| row.get("page_id"))
+        .collect())
+    }
+
+    // Pages are locked in a fixed order, so concurrent changes to shared blocks can not deadlock
+    pub async fn record_multiple(conn: &mut PgConnection, page_ids: &[Uuid]) -> Result<()> {
+        let mut page_ids = page_ids.to_vec();
+        page_ids.sort();
+
+        for page_id in page_ids {
+            Self::record(conn, &page_id).await?;
+        }
+
+        Ok(())
+    }
+
+    // Restoring brings back the blocks of the page along with their markdown and ingredients,
+    // and is recorded as a new revision
+    async fn restore(tx: &mut PgTransaction<'_>, page_id: &Uuid, id: &Uuid) -> Result<Page> {
+        let revision = Self::get_by_id(tx, page_id, id).await?;
+        let snapshot = match revision.data.snapshot {
+            Some(snapshot) => snapshot,
+            None => return Err(DbError::NotFound.into()),
+        };
+        let contents: Snapshot = serde_json::from_value(snapshot.clone())?;
+
+        let block_ids: Vec<Uuid> = contents.data.blocks.iter().map(|block| block.id).collect();
+        let existing: i64 = sqlx::query(
+            "
+            SELECT COUNT(*) AS count
+            FROM public.blocks
+            WHERE id = ANY($1)
+            ",
+        )
+        .bind(&block_ids)
+        .fetch_one(&mut **tx)
+        .await?
+        .get("count");
+
+        if existing as usize != block_ids.len() {
+            return Err(DbError::Validation(
+                "revision contains blocks that no longer exist".to_string(),
+            )
+            .into());
+        }
+
+        for block in contents.data.blocks {
+            let Some(data) = block.data else {
+                return Err(DbError::Validation(
+                    "revision contains blocks without contents".to_string(),
+                )
+                .into());
+            };
+
+            match data.kind {
+                SnapshotBlockKind::Markdown { id, data } => {
+                    Self::restore_markdown(tx, &id, &data).await?
+                }
+                SnapshotBlockKind::IngredientCollection { id, data } => {
+                    Self::restore_ingredients(tx, &id, &data).await?
+                }
+                SnapshotBlockKind::Steps { steps } => {
+                    Self::restore_steps(tx, &block.id, &steps).await?
+                }
+                SnapshotBlockKind::Image { id } => Self::restore_image(tx, &block.id, &id).await?,
+                SnapshotBlockKind::PageReference { id } => {
+                    Self::restore_page_reference(tx, &block.id, &id).await?
+                }
+                SnapshotBlockKind::Checklist { id, data } => {
+                    Self::restore_checklist(tx, &block.id, &id, &data).await?
+                }
+                SnapshotBlockKind::Table { id, data } => {
+                    Self::restore_table(tx, &block.id, &id, &data).await?
+                }
+            }
+        }
+
+        let update: PageUpdate = serde_json::from_value(json!({
+            "type": snapshot["data"]["type"],
+            "name": snapshot["data"]["name"],
+            "recipe": snapshot["data"]["recipe"],
+            "blocks": block_ids
+                .iter()
+                .map(|block_id| json!({ "id": block_id }))
+                .collect::<Vec<_>>(),
+        }))?;
+
+        PageDbPostgres::update_by_id(tx, page_id, update).await
+    }
+
+    async fn restore_markdown(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        markdown: &SnapshotMarkdown,
+    ) -> Result<()> {
+        sqlx::query(
+            "
+            UPDATE public.markdown
+            SET markdown = $2,
+                ts_updated = NOW()
+            WHERE id = $1 AND markdown <> $2
+            ",
+        )
+        .bind(id)
+        .bind(&markdown.markdown)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    // Ingredients keep their ids, so steps and lists referring to them stay intact
+    async fn restore_ingredients(
+        tx: &mut PgTransaction<'_>,
+        collection_id: &Uuid,
+        collection: &SnapshotIngredientCollection,
+    ) -> Result<()> {
+        let ids: Vec<Uuid> = collection
+            .ingredients
+            .iter()
+            .map(|ingredient| ingredient.id)
+            .collect();
+
+        // Ingredients added since would otherwise take their list items with them
+        let current: Vec<(Uuid, bool)> = sqlx::query(
+            "
+            SELECT
+                ingredients.id,
+                EXISTS (
+                    SELECT 1
+                    FROM public.ingredient_list_items
+                    WHERE ingredient_list_items.ingredient_id = ingredients.id
+                ) AS listed
+            FROM public.ingredients
+            WHERE ingredient_collection_id = $1
+            ORDER BY ingredients.id
+            ",
+        )
+        .bind(collection_id)
+        .fetch_all(&mut **tx)
+        .await?
+        .iter()
+        .map(|row| (row.get("id"), row.get("listed")))
+        .collect();
+
+        let removed = revisions::removals(&current, &ids)
+            .map_err(|_| DbError::Validation(RESTORE_LISTED_MESSAGE.to_string()))?;
+
+        sqlx::query(
+            "
+            DELETE FROM public.ingredients
+            WHERE id = ANY($1)
+            ",
+        )
+        .bind(&removed)
+        .execute(&mut **tx)
+        .await?;
+
+        for ingredient in &collection.ingredients {
+            sqlx::query(
+                "
+                INSERT INTO public.ingredients (id, ingredient_collection_id, product_id, quantity, unit)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (id) DO UPDATE
+                SET product_id = EXCLUDED.product_id,
+                    quantity = EXCLUDED.quantity,
+                    unit = EXCLUDED.unit,
+                    ts_updated = NOW()
+                WHERE
+                    (ingredients.product_id, ingredients.quantity, ingredients.unit) IS DISTINCT FROM
+                    (EXCLUDED.product_id, EXCLUDED.quantity, EXCLUDED.unit)
+                ",
+            )
+            .bind(ingredient.id)
+            .bind(collection_id)
+            .bind(ingredient.data.product.id)
+            .bind(ingredient.data.quantity)
+            .bind(&ingredient.data.unit)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn restore_steps(
+        tx: &mut PgTransaction<'_>,
+        block_id: &Uuid,
+        steps: &[Step],
+    ) -> Result<()> {
+        let link_id: Uuid = sqlx::query(
+            "
+            SELECT steps_block_id
+            FROM public.blocks
+            WHERE id = $1 AND steps_block_id IS NOT NULL
+            ",
+        )
+        .bind(block_id)
+        .fetch_one(&mut **tx)
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::Validation(
+                RESTORE_REFERENCE_MESSAGE.to_string(),
+            )),
+            _ => error.into(),
+        })
+        .await?
+        .get("steps_block_id");
+
+        sqlx::query(
+            "
+            DELETE FROM public.steps
+            WHERE steps_block_id = $1
+            ",
+        )
+        .bind(link_id)
+        .execute(&mut **tx)
+        .await?;
+
+        BlockDbPostgres::insert_steps(tx, &link_id, steps).await
+    }
+
+    async fn restore_image(
+        tx: &mut PgTransaction<'_>,
+        block_id: &Uuid,
+        image_id: &Uuid,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            "
+            UPDATE public.image_blocks
+            SET image_id = images.id,
+                ts_updated = NOW()
+            FROM public.blocks, public.images
+            WHERE
+                blocks.id = $1 AND
+                image_blocks.id = blocks.image_block_id AND
+                images.id = $2
+            ",
+        )
+        .bind(block_id)
+        .bind(image_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::Validation(RESTORE_REFERENCE_MESSAGE.to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    async fn restore_page_reference(
+        tx: &mut PgTransaction<'_>,
+        block_id: &Uuid,
+        page_id: &Uuid,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            "
+            UPDATE public.page_reference_blocks
+            SET page_id = pages.id,
+                ts_updated = NOW()
+            FROM public.blocks, public.pages
+            WHERE
+                blocks.id = $1 AND
+                page_reference_blocks.id = blocks.page_reference_block_id AND
+                pages.id = $2
+            ",
+        )
+        .bind(block_id)
+        .bind(page_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::Validation(RESTORE_REFERENCE_MESSAGE.to_string()).into());
+        }
+
+        Ok(())
+    }
+
+    // Checklist items keep their ids, like ingredients
+    async fn restore_checklist(
+        tx: &mut PgTransaction<'_>,
+        block_id: &Uuid,
+        checklist_id: &Uuid,
+        checklist: &SnapshotChecklist,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            "
+            UPDATE public.checklist_blocks
+            SET checklist_id = checklists.id,
+                ts_updated = NOW()
+            FROM public.blocks, public.checklists
+            WHERE
+                blocks.id = $1 AND
+                checklist_blocks.id = blocks.checklist_block_id AND
+                checklists.id = $2
+            ",
+        )
+        .bind(block_id)
+        .bind(checklist_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::Validation(RESTORE_REFERENCE_MESSAGE.to_string()).into());
+        }
+
+        let ids: Vec<Uuid> = checklist.items.iter().map(|item| item.id).collect();
+
+        sqlx::query(
+            "
+            DELETE FROM public.checklist_items
+            WHERE checklist_id = $1 AND id <> ALL($2)
+            ",
+        )
+        .bind(checklist_id)
+        .bind(&ids)
+        .execute(&mut **tx)
+        .await?;
+
+        for (seq, item) in checklist.items.iter().enumerate() {
+            sqlx::query(
+                "
+                INSERT INTO public.checklist_items (id, checklist_id, sequence_number, text, checked)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (id) DO UPDATE
+                SET sequence_number = EXCLUDED.sequence_number,
+                    text = EXCLUDED.text,
+                    checked = EXCLUDED.checked,
+                    ts_updated = NOW()
+                WHERE
+                    (checklist_items.sequence_number, checklist_items.text, checklist_items.checked)
+                    IS DISTINCT FROM
+                    (EXCLUDED.sequence_number, EXCLUDED.text, EXCLUDED.checked)
+                ",
+            )
+            .bind(item.id)
+            .bind(checklist_id)
+            .bind(seq as i32)
+            .bind(&item.data.text)
+            .bind(item.data.checked)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn restore_table(
+        tx: &mut PgTransaction<'_>,
+        block_id: &Uuid,
+        table_id: &Uuid,
+        table: &SnapshotTable,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            "
+            UPDATE public.table_blocks
+            SET table_id = tables.id,
+                ts_updated = NOW()
+            FROM public.blocks, public.tables
+            WHERE
+                blocks.id = $1 AND
+                table_blocks.id = blocks.table_block_id AND
+                tables.id = $2
+            ",
+        )
+        .bind(block_id)
+        .bind(table_id)
+        .execute(&mut **tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(DbError::Validation(RESTORE_REFERENCE_MESSAGE.to_string()).into());
+        }
+
+        TableDbPostgres::write_contents(tx, table_id, &table.columns, &table.rows).await
+    }
+}
@@ -1,10 +1,13 @@
+use std::collections::HashSet;
+use std::future::Future;
 use std::pin::Pin;
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{stream::Peekable, Stream, StreamExt, TryStreamExt};
+use pulldown_cmark::Options;
 use serde::{Deserialize, Serialize};
-use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgConnection, PgPool, PgTransaction, Row};
 use uuid::Uuid;
 
 use crate::{
@@ -12,33 +15,59 @@ use crate::{
     utilities::{
         markdown::markdown_to_html,
         modifier::{Create, Modifier, Query, Reference, Update},
+        search::escape_like,
+        tables::table_to_html,
     },
 };
 
 use super::{
-    blocks::{BlockDataTemplate, BlockKindTemplate, BlockReference},
-    ingredient_collections::{IngredientCollectionDataTemplate, IngredientCollectionReference},
-    ingredients::{IngredientDataTemplate, IngredientReference},
-    list_items::ListItemReference,
+    blocks::{BlockDataTemplate, BlockDbPostgres, BlockKindTemplate, BlockReference, Step},
+    checklist_items::{ChecklistItem, ChecklistItemDataTemplate},
+    checklists::{ChecklistData, ChecklistReference},
+    images::{ImageDataTemplate, ImageReference},
+    ingredient_collections::{
+        IngredientCollectionDataTemplate, IngredientCollectionDbPostgres,
+        IngredientCollectionReference,
+    },
+    ingredients::{
+        IngredientCreate, IngredientDataTemplate, IngredientDbPostgres, IngredientReference,
+    },
+    list_items::{
+        ListItem, ListItemCreate, ListItemDbPostgres, ListItemKindTemplate, ListItemReference,
+    },
     lists::{ListDataTemplate, ListItemReferences, ListReference},
-    markdown::{MarkdownDataTemplate, MarkdownReference},
+    markdown::{MarkdownCreate, MarkdownDataTemplate, MarkdownDbPostgres, MarkdownReference},
+    page_revisions::PageRevisionDbPostgres,
     products::{ProductDataTemplate, ProductReference},
+    tables::{TableDataTemplate, TableDbPostgres, TableReference},
     DbError,
 };
 
 #[trait_variant::make(Send)]
 pub trait PageDb {
     async fn get_multiple(&mut self, params: SearchParams) -> Result<Vec<Page>>;
-    async fn get_by_id(&mut self, id: &Uuid) -> Result<Page>;
+    async fn get_by_id(&mut self, id: &Uuid, params: PageParams) -> Result<Page>;
     async fn create_multiple(&mut self, items: Vec<PageCreate>) -> Result<Vec<Page>>;
     async fn update_by_id(&mut self, id: &Uuid, item: PageUpdate) -> Result<Page>;
     async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
+    async fn add_to_list(&mut self, id: &Uuid, add: PageAddToList) -> Result<Vec<ListItem>>;
+    async fn insert_block(&mut self, id: &Uuid, insert: PageBlockInsert) -> Result<PageBlock>;
+    async fn move_block(
+        &mut self,
+        id: &Uuid,
+        link_id: &Uuid,
+        block_move: PageBlockMove,
+    ) -> Result<Page>;
+    async fn remove_block(&mut self, id: &Uuid, link_id: &Uuid) -> Result<Page>;
 }
 
+// Referenced pages are never expanded or followed deeper than this
+const MAX_REFERENCE_DEPTH: u32 = 5;
+
 pub type Page = PageTemplate<Query>;
 pub type PageCreate = PageDataTemplate<Create>;
 pub type PageUpdate = PageDataTemplate<Update>;
-// pub type PageReference = PageTemplate<Reference>;
+pub type PageBlock = PageBlockTemplate<Query>;
 
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct PageTemplate<M: Modifier> {
@@ -51,6 +80,19 @@ pub struct PageTemplate<M: Modifier> {
     pub data: M::Data<PageDataTemplate<M>>,
 }
 
+// Same as `PageTemplate<Reference>`, which cannot be used as pages are made of blocks that may
+// reference pages again, sending trait resolution in circles
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct PageReference {
+    pub id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts_created: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts_updated: Option<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<PageDataTemplate<Reference>>,
+}
+
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct PageDataTemplate<M: Modifier> {
     #[serde(skip_serializing_if = "M::skip_data")]
@@ -59,26 +101,289 @@ pub struct PageDataTemplate<M: Modifier> {
     pub name: M::Data<String>,
     #[serde(skip_serializing_if = "M::skip_data")]
     pub blocks: M::Data<Vec<PageBlockTemplate<M>>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub recipe: M::Nullable<RecipeMetadataTemplate<M>>,
+}
+
+// Times are in minutes
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct RecipeMetadataTemplate<M: Modifier> {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub prep_time: M::Nullable<i32>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub cook_time: M::Nullable<i32>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub total_time: M::Nullable<i32>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub servings: M::Nullable<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub servings_unit: M::Nullable<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub difficulty: M::Nullable<RecipeDifficulty>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub cuisine: M::Nullable<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub source_url: M::Nullable<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub source_author: M::Nullable<String>,
+}
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[sqlx(type_name = "recipe_difficulty", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RecipeDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl From<RecipeMetadataTemplate<Create>> for RecipeMetadataTemplate<Query> {
+    fn from(value: RecipeMetadataTemplate<Create>) -> Self {
+        Self {
+            prep_time: value.prep_time,
+            cook_time: value.cook_time,
+            total_time: value.total_time,
+            servings: value.servings,
+            servings_unit: value.servings_unit,
+            difficulty: value.difficulty,
+            cuisine: value.cuisine,
+            source_url: value.source_url,
+            source_author: value.source_author,
+        }
+    }
+}
+
+impl RecipeMetadataTemplate<Query> {
+    fn from_row(row: &PgRow) -> Option<Self> {
+        row.get::<Option<Uuid>, _>("recipe_page_id")?;
+
+        Some(Self {
+            prep_time: row.get("recipe_prep_time"),
+            cook_time: row.get("recipe_cook_time"),
+            total_time: row.get("recipe_total_time"),
+            servings: row.get("recipe_servings"),
+            servings_unit: row.get("recipe_servings_unit"),
+            difficulty: row.get("recipe_difficulty"),
+            cuisine: row.get("recipe_cuisine"),
+            source_url: row.get("recipe_source_url"),
+            source_author: row.get("recipe_source_author"),
+        })
+    }
 }
 
-#[derive(sqlx::Type, Debug, Clone, Serialize, Deserialize)]
+#[derive(sqlx::Type, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[sqlx(type_name = "page_type", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum PageType {
     Recipe,
+    Note,
+    Article,
+    MealPlan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageBlockKind {
+    IngredientCollection,
+    Markdown,
+    Steps,
+    Image,
+    PageReference,
+    Checklist,
+    Table,
+}
+
+impl PageBlockKind {
+    fn name(&self) -> &'static str {
+        match self {
+            PageBlockKind::IngredientCollection => "ingredient collection",
+            PageBlockKind::Markdown => "markdown",
+            PageBlockKind::Steps => "steps",
+            PageBlockKind::Image => "image",
+            PageBlockKind::PageReference => "page reference",
+            PageBlockKind::Checklist => "checklist",
+            PageBlockKind::Table => "table",
+        }
+    }
+}
+
+const RECIPE_METADATA_MESSAGE: &str = "only recipe pages may have recipe metadata";
+
+pub struct PageSchema {
+    // Any block kind is allowed when there is no restriction
+    pub allowed: Option<&'static [PageBlockKind]>,
+    pub required: &'static [PageBlockKind],
+}
+
+impl PageType {
+    pub fn schema(&self) -> PageSchema {
+        match self {
+            PageType::Recipe => PageSchema {
+                allowed: None,
+                required: &[PageBlockKind::IngredientCollection],
+            },
+            PageType::Note => PageSchema {
+                allowed: None,
+                required: &[],
+            },
+            PageType::Article => PageSchema {
+                allowed: None,
+                required: &[],
+            },
+            // Meal plans are meant to consist of references to other pages only
+            PageType::MealPlan => PageSchema {
+                allowed: Some(&[PageBlockKind::PageReference]),
+                required: &[],
+            },
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            PageType::Recipe => "recipe",
+            PageType::Note => "note",
+            PageType::Article => "article",
+            PageType::MealPlan => "meal plan",
+        }
+    }
+
+    pub fn validate(&self, kinds: &[PageBlockKind]) -> std::result::Result<(), DbError> {
+        let schema = self.schema();
+
+        if let Some(allowed) = schema.allowed {
+            if let Some(kind) = kinds.iter().find(|kind| !allowed.contains(kind)) {
+                return Err(DbError::Validation(format!(
+                    "{} pages may not contain {} blocks",
+                    self.name(),
+                    kind.name()
+                )));
+            }
+        }
+
+        if let Some(kind) = schema.required.iter().find(|kind| !kinds.contains(kind)) {
+            return Err(DbError::Validation(format!(
+                "{} pages require at least one {} block",
+                self.name(),
+                kind.name()
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct PageBlockTemplate<M: Modifier> {
-    #[serde(skip)]
+    #[serde(skip_deserializing)]
+    #[serde(skip_serializing_if = "M::skip_data")]
     pub link_id: M::Data<Uuid>,
     #[serde(flatten)]
     pub block: M::Data<BlockReference>,
 }
 
+#[derive(Default, Debug, Deserialize)]
+pub struct PageParams {
+    // How many levels of referenced pages are included in full
+    #[serde(default)]
+    pub depth: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageAddToList {
+    pub list_id: Uuid,
+}
+
+// Blocks are appended when no position is given
+#[derive(Debug, Deserialize)]
+pub struct PageBlockInsert {
+    #[serde(flatten)]
+    pub block: PageBlockSource,
+    pub position: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PageBlockSource {
+    Existing { block_id: Uuid },
+    // The contents of the block are created along with it
+    Inline { kind: PageBlockInline },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PageBlockInline {
+    Markdown {
+        markdown: String,
+    },
+    IngredientCollection {
+        #[serde(default)]
+        ingredients: Vec<IngredientCreate>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageBlockMove {
+    pub position: usize,
+}
+
+const BLOCK_POSITION_MESSAGE: &str = "block position is out of range";
+
+impl From<Page> for PageReference {
+    fn from(value: Page) -> Self {
+        Self {
+            id: value.id,
+            ts_created: Some(value.ts_created),
+            ts_updated: Some(value.ts_updated),
+            data: Some(PageDataTemplate {
+                r#type: Some(value.data.r#type),
+                name: Some(value.data.name),
+                blocks: Some(
+                    value
+                        .data
+                        .blocks
+                        .into_iter()
+                        .map(|page_block| PageBlockTemplate {
+                            link_id: Some(page_block.link_id),
+                            block: Some(page_block.block),
+                        })
+                        .collect(),
+                ),
+                recipe: value.data.recipe.map(|recipe| RecipeMetadataTemplate {
+                    prep_time: recipe.prep_time,
+                    cook_time: recipe.cook_time,
+                    total_time: recipe.total_time,
+                    servings: recipe.servings,
+                    servings_unit: recipe.servings_unit,
+                    difficulty: recipe.difficulty,
+                    cuisine: recipe.cuisine,
+                    source_url: recipe.source_url,
+                    source_author: recipe.source_author,
+                }),
+            }),
+        }
+    }
+}
+
 #[derive(Default, Debug, Deserialize)]
 pub struct SearchParams {
+    // Matches names containing or resembling it, ignoring accents and case
+    pub name: Option<String>,
     pub r#type: Option<PageType>,
+    // Total time falls back to the sum of prep and cook time
+    pub max_total_time: Option<i32>,
+    pub difficulty: Option<RecipeDifficulty>,
+    // Cuisine and author match when they contain the given text
+    pub cuisine: Option<String>,
+    pub author: Option<String>,
 }
 
 impl FromRow<'_, PgRow> for Page {
@@ -91,6 +396,7 @@ impl FromRow<'_, PgRow> for Page {
                 r#type: row.get("type"),
                 name: row.get("name"),
                 blocks: Vec::new(),
+                recipe: None,
             },
         })
     }
@@ -155,6 +461,7 @@ impl Page {
                         items.push(Self::collect_page_block(&next, rest, summary).await?);
                     }
                 },
+                recipe: RecipeMetadataTemplate::from_row(first),
             },
         })
     }
@@ -183,6 +490,35 @@ impl Page {
                                     link_id: id,
                                     markdown: Some(Self::collect_markdown(first, rest).await?),
                                 }
+                            } else if let Some(id) = first.get("image_block_id") {
+                                BlockKindTemplate::Image {
+                                    link_id: id,
+                                    image: Some(Self::collect_image(first, rest).await?),
+                                }
+                            } else if let Some(id) = first.get("page_reference_block_id") {
+                                BlockKindTemplate::PageReference {
+                                    link_id: id,
+                                    page: Some(Self::collect_page_reference(first, rest).await?),
+                                }
+                            } else if let Some(id) = first.get("checklist_block_id") {
+                                BlockKindTemplate::Checklist {
+                                    link_id: id,
+                                    checklist: Some(Self::collect_checklist(first, rest).await?),
+                                }
+                            } else if let Some(id) = first.get("table_block_id") {
+                                // Contents are collected separately
+                                BlockKindTemplate::Table {
+                                    link_id: id,
+                                    table: Some(TableReference {
+                                        id: first.get("table_id"),
+                                        ..Default::default()
+                                    }),
+                                }
+                            } else if let Some(id) = first.get("steps_block_id") {
+                                BlockKindTemplate::Steps {
+                                    link_id: id,
+                                    steps: Some(Self::collect_steps(first, rest).await?),
+                                }
                             } else {
                                 panic!("unreachable!")
                             }
@@ -237,6 +573,8 @@ impl Page {
         Ok(IngredientReference {
             id: first.get("ingredient_id"),
             data: Some(IngredientDataTemplate {
+                quantity: first.get("ingredient_quantity"),
+                unit: first.get("ingredient_unit"),
                 product: Some(ProductReference {
                     id: first.get("product_id"),
                     data: Some(ProductDataTemplate {
@@ -320,6 +658,107 @@ impl Page {
         })
     }
 
+    async fn collect_page_reference(
+        first: &PgRow,
+        _rest: &mut Pin<&mut Peekable<impl Stream<Item = Result<PgRow, sqlx::Error>>>>,
+    ) -> Result<PageReference> {
+        Ok(PageReference {
+            id: first.get("referenced_page_id"),
+            data: Some(PageDataTemplate {
+                r#type: Some(first.get("referenced_page_type")),
+                name: Some(first.get("referenced_page_name")),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    async fn collect_image(
+        first: &PgRow,
+        _rest: &mut Pin<&mut Peekable<impl Stream<Item = Result<PgRow, sqlx::Error>>>>,
+    ) -> Result<ImageReference> {
+        Ok(ImageReference {
+            id: first.get("image_id"),
+            data: Some(ImageDataTemplate {
+                name: first.get("image_name"),
+                content_type: Some(first.get("image_content_type")),
+                size: Some(first.get("image_size")),
+                width: Some(first.get("image_width")),
+                height: Some(first.get("image_height")),
+                thumbnails: Some(first.get("image_thumbnails")),
+            }),
+            ..Default::default()
+        })
+    }
+
+    async fn collect_steps(
+        first: &PgRow,
+        rest: &mut Pin<&mut Peekable<impl Stream<Item = Result<PgRow, sqlx::Error>>>>,
+    ) -> Result<Vec<Step>> {
+        let mut items = Vec::new();
+
+        if first.get::<Option<Uuid>, _>("step_id").is_some() {
+            items.push(Step::from_row(first)?);
+        }
+
+        loop {
+            if !next_matches_first!(rest, first, "id", "block_id") {
+                break Ok(items);
+            }
+
+            let next = match rest.try_next().await? {
+                Some(next) => next,
+                None => break Ok(items),
+            };
+
+            items.push(Step::from_row(&next)?);
+        }
+    }
+
+    async fn collect_checklist(
+        first: &PgRow,
+        rest: &mut Pin<&mut Peekable<impl Stream<Item = Result<PgRow, sqlx::Error>>>>,
+    ) -> Result<ChecklistReference> {
+        Ok(ChecklistReference {
+            id: first.get("checklist_id"),
+            data: Some(ChecklistData {
+                items: {
+                    let mut items = Vec::new();
+
+                    if first.get::<Option<Uuid>, _>("checklist_item_id").is_some() {
+                        items.push(Self::collect_checklist_item(first)?);
+                    }
+
+                    loop {
+                        if !next_matches_first!(rest, first, "id", "block_id") {
+                            break items;
+                        }
+
+                        let next = match rest.try_next().await? {
+                            Some(next) => next,
+                            None => break items,
+                        };
+
+                        items.push(Self::collect_checklist_item(&next)?);
+                    }
+                },
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn collect_checklist_item(row: &PgRow) -> Result<ChecklistItem> {
+        Ok(ChecklistItem {
+            id: row.get("checklist_item_id"),
+            ts_created: row.get("checklist_item_ts_created"),
+            ts_updated: row.get("checklist_item_ts_updated"),
+            data: ChecklistItemDataTemplate {
+                text: row.get("checklist_item_text"),
+                checked: row.get("checklist_item_checked"),
+            },
+        })
+    }
+
     async fn collect_markdown(
         first: &PgRow,
         _rest: &mut Pin<&mut Peekable<impl Stream<Item = Result<PgRow, sqlx::Error>>>>,
@@ -328,7 +767,7 @@ impl Page {
             id: first.get("markdown_id"),
             data: Some({
                 let markdown = first.get::<String, _>("markdown");
-                let html = markdown_to_html(&markdown);
+                let html = markdown_to_html(&markdown, Options::empty());
                 MarkdownDataTemplate {
                     markdown: Some(markdown),
                     html: Some(html),
@@ -361,29 +800,79 @@ impl PageDb for PageDbPostgres<'_> {
                 pages.ts_updated,
                 pages.type,
                 pages.name,
+                recipe_metadata.page_id AS recipe_page_id,
+                recipe_metadata.prep_time AS recipe_prep_time,
+                recipe_metadata.cook_time AS recipe_cook_time,
+                recipe_metadata.total_time AS recipe_total_time,
+                recipe_metadata.servings AS recipe_servings,
+                recipe_metadata.servings_unit AS recipe_servings_unit,
+                recipe_metadata.difficulty AS recipe_difficulty,
+                recipe_metadata.cuisine AS recipe_cuisine,
+                recipe_metadata.source_url AS recipe_source_url,
+                recipe_metadata.source_author AS recipe_source_author,
                 page_blocks.id AS page_block_id,
                 blocks.id AS block_id
 
             FROM public.pages
+                LEFT JOIN public.recipe_metadata
+                    ON pages.id = recipe_metadata.page_id
                 LEFT JOIN public.page_blocks
                     ON pages.id = page_blocks.page_id
                 LEFT JOIN public.blocks
                     ON page_blocks.block_id = blocks.id
 
-            WHERE pages.type = $1 OR $1 IS NULL
+            WHERE
+                (pages.type = $1 OR $1 IS NULL) AND
+                (
+                    COALESCE(
+                        recipe_metadata.total_time,
+                        recipe_metadata.prep_time + recipe_metadata.cook_time
+                    ) <= $2 OR
+                    $2 IS NULL
+                ) AND
+                (recipe_metadata.difficulty = $3 OR $3 IS NULL) AND
+                (
+                    normalize_text(recipe_metadata.cuisine) LIKE '%' || normalize_text($4) || '%' OR
+                    $4 IS NULL
+                ) AND
+                (
+                    normalize_text(recipe_metadata.source_author) LIKE
+                        '%' || normalize_text($5) || '%' OR
+                    $5 IS NULL
+                ) AND
+                (
+                    normalize_text(pages.name) LIKE '%' || normalize_text($6) || '%' OR
+                    normalize_text(pages.name) % normalize_text($7) OR
+                    $7 IS NULL
+                )
             ORDER BY pages.name
             ",
         )
         .bind(params.r#type)
+        .bind(params.max_total_time)
+        .bind(params.difficulty)
+        .bind(params.cuisine.as_deref().map(escape_like))
+        .bind(params.author.as_deref().map(escape_like))
+        .bind(params.name.as_deref().map(escape_like))
+        .bind(params.name)
         .fetch(&mut *conn);
 
         Page::collect_pages(stream, true).await
     }
 
-    async fn get_by_id(&mut self, id: &Uuid) -> Result<Page> {
+    async fn get_by_id(&mut self, id: &Uuid, params: PageParams) -> Result<Page> {
         let mut conn = self.pool.acquire().await?;
 
-        Self::get_by_id(&mut *conn, id).await
+        let mut item = Self::get_by_id(&mut conn, id).await?;
+        let blocks = item
+            .data
+            .blocks
+            .iter_mut()
+            .map(|page_block| &mut page_block.block)
+            .collect();
+        Self::expand(&mut conn, blocks, params.depth.min(MAX_REFERENCE_DEPTH)).await?;
+
+        Ok(item)
     }
 
     async fn create_multiple(&mut self, items: Vec<PageCreate>) -> Result<Vec<Page>> {
@@ -394,8 +883,8 @@ impl PageDb for PageDbPostgres<'_> {
             match Self::create(&mut tx, item).await {
                 Ok(item) => created.push(item),
                 Err(error) => {
-                    tx.commit().await?;
-                    return Err(error.into());
+                    tx.rollback().await?;
+                    return Err(error);
                 }
             };
         }
@@ -411,8 +900,8 @@ impl PageDb for PageDbPostgres<'_> {
         let updated = match Self::update_by_id(&mut tx, id, item).await {
             Ok(item) => item,
             Err(error) => {
-                tx.commit().await?;
-                return Err(error.into());
+                tx.rollback().await?;
+                return Err(error);
             }
         };
 
@@ -424,7 +913,8 @@ impl PageDb for PageDbPostgres<'_> {
     async fn delete_by_id(&mut self, id: &Uuid) -> Result<()> {
         let mut conn = self.pool.acquire().await?;
 
-        // Relying on cascaded delete regarding corresponding page blocks
+        // Relying on cascaded delete regarding corresponding page blocks, pages that are still
+        // referenced by page reference blocks are kept
         if sqlx::query(
             "
             DELETE FROM public.pages
@@ -433,7 +923,13 @@ impl PageDb for PageDbPostgres<'_> {
         )
         .bind(id)
         .execute(&mut *conn)
-        .await?
+        .await
+        .map_err(|error| match error {
+            sqlx::Error::Database(ref database) if database.is_foreign_key_violation() => {
+                Into::<anyhow::Error>::into(DbError::InvalidOperation)
+            }
+            _ => error.into(),
+        })?
         .rows_affected()
             == 0
         {
@@ -442,13 +938,79 @@ impl PageDb for PageDbPostgres<'_> {
 
         Ok(())
     }
+
+    async fn add_to_list(&mut self, id: &Uuid, add: PageAddToList) -> Result<Vec<ListItem>> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = match Self::add_to_list(&mut tx, id, add).await {
+            Ok(created) => created,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn insert_block(&mut self, id: &Uuid, insert: PageBlockInsert) -> Result<PageBlock> {
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = match Self::insert_block(&mut tx, id, insert).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(inserted)
+    }
+
+    async fn move_block(
+        &mut self,
+        id: &Uuid,
+        link_id: &Uuid,
+        block_move: PageBlockMove,
+    ) -> Result<Page> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = match Self::move_block(&mut tx, id, link_id, block_move).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn remove_block(&mut self, id: &Uuid, link_id: &Uuid) -> Result<Page> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = match Self::remove_block(&mut tx, id, link_id).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
 }
 
 impl PageDbPostgres<'_> {
-    async fn get_by_id<'c, E>(executor: E, id: &Uuid) -> Result<Page>
-    where
-        E: PgExecutor<'c>,
-    {
+    pub async fn get_by_id(conn: &mut PgConnection, id: &Uuid) -> Result<Page> {
         let stream = sqlx::query(
             "
             SELECT
@@ -457,6 +1019,16 @@ impl PageDbPostgres<'_> {
                 pages.ts_updated,
                 pages.type,
                 pages.name,
+                recipe_metadata.page_id AS recipe_page_id,
+                recipe_metadata.prep_time AS recipe_prep_time,
+                recipe_metadata.cook_time AS recipe_cook_time,
+                recipe_metadata.total_time AS recipe_total_time,
+                recipe_metadata.servings AS recipe_servings,
+                recipe_metadata.servings_unit AS recipe_servings_unit,
+                recipe_metadata.difficulty AS recipe_difficulty,
+                recipe_metadata.cuisine AS recipe_cuisine,
+                recipe_metadata.source_url AS recipe_source_url,
+                recipe_metadata.source_author AS recipe_source_author,
                 page_blocks.id AS page_block_id,
                 page_blocks.sequence_number AS page_block_seq,
                 blocks.id AS block_id,
@@ -464,6 +1036,8 @@ impl PageDbPostgres<'_> {
                 ingredient_collection_blocks.id AS ingredient_collection_block_id,
                 ingredient_collections.id AS ingredient_collection_id,
                 ingredients.id AS ingredient_id,
+                ingredients.quantity AS ingredient_quantity,
+                ingredients.unit AS ingredient_unit,
                 ingredient_lists.id AS ingredient_list_id,
                 ingredient_lists.name AS ingredient_list_name,
                 ingredient_list_items.id AS ingredient_list_item_id,
@@ -473,9 +1047,47 @@ impl PageDbPostgres<'_> {
 
                 markdown_blocks.id AS markdown_block_id,
                 markdown.id AS markdown_id,
-                markdown.markdown AS markdown
+                markdown.markdown AS markdown,
+
+                steps_blocks.id AS steps_block_id,
+                steps.id AS step_id,
+                steps.text AS step_text,
+                steps.duration AS step_duration,
+                ARRAY(
+                    SELECT ingredient_id
+                    FROM public.step_ingredients
+                    WHERE step_id = steps.id
+                    ORDER BY sequence_number
+                ) AS step_ingredient_ids,
+
+                image_blocks.id AS image_block_id,
+                images.id AS image_id,
+                images.name AS image_name,
+                images.content_type AS image_content_type,
+                images.size AS image_size,
+                images.width AS image_width,
+                images.height AS image_height,
+                images.thumbnails AS image_thumbnails,
+
+                page_reference_blocks.id AS page_reference_block_id,
+                referenced_pages.id AS referenced_page_id,
+                referenced_pages.type AS referenced_page_type,
+                referenced_pages.name AS referenced_page_name,
+
+                checklist_blocks.id AS checklist_block_id,
+                checklist_blocks.checklist_id AS checklist_id,
+                checklist_items.id AS checklist_item_id,
+                checklist_items.ts_created AS checklist_item_ts_created,
+                checklist_items.ts_updated AS checklist_item_ts_updated,
+                checklist_items.text AS checklist_item_text,
+                checklist_items.checked AS checklist_item_checked,
+
+                table_blocks.id AS table_block_id,
+                table_blocks.table_id AS table_id
 
             FROM public.pages
+                LEFT JOIN public.recipe_metadata
+                    ON pages.id = recipe_metadata.page_id
                 LEFT JOIN public.page_blocks
                     ON pages.id = page_blocks.page_id
                 LEFT JOIN public.blocks
@@ -501,23 +1113,96 @@ impl PageDbPostgres<'_> {
                 LEFT JOIN public.markdown
                     ON markdown_blocks.markdown_id = markdown.id
 
+                LEFT JOIN public.steps_blocks
+                    ON blocks.steps_block_id = steps_blocks.id
+                LEFT JOIN public.steps
+                    ON steps_blocks.id = steps.steps_block_id
+
+                LEFT JOIN public.image_blocks
+                    ON blocks.image_block_id = image_blocks.id
+                LEFT JOIN public.images
+                    ON image_blocks.image_id = images.id
+
+                LEFT JOIN public.page_reference_blocks
+                    ON blocks.page_reference_block_id = page_reference_blocks.id
+                LEFT JOIN public.pages AS referenced_pages
+                    ON page_reference_blocks.page_id = referenced_pages.id
+
+                LEFT JOIN public.checklist_blocks
+                    ON blocks.checklist_block_id = checklist_blocks.id
+                LEFT JOIN public.checklist_items
+                    ON checklist_blocks.checklist_id = checklist_items.checklist_id
+
+                LEFT JOIN public.table_blocks
+                    ON blocks.table_block_id = table_blocks.id
+
             WHERE pages.id = $1
             ORDER BY
                 page_blocks.sequence_number,
-                products.name
+                products.name,
+                steps.sequence_number,
+                checklist_items.sequence_number
             ",
         )
         .bind(id)
-        .fetch(executor);
+        .fetch(&mut *conn);
 
-        match Page::collect_pages(stream, false).await?.pop() {
-            Some(item) => Ok(item),
-            None => Err((DbError::NotFound).into()),
+        let mut item = match Page::collect_pages(stream, false).await?.pop() {
+            Some(item) => item,
+            None => return Err((DbError::NotFound).into()),
+        };
+
+        Self::collect_tables(conn, &mut item).await?;
+
+        Ok(item)
+    }
+
+    async fn collect_tables(conn: &mut PgConnection, item: &mut Page) -> Result<()> {
+        let mut tables: Vec<&mut TableReference> = item
+            .data
+            .blocks
+            .iter_mut()
+            .filter_map(
+                |page_block| match page_block.block.data.as_mut()?.kind.as_mut()? {
+                    BlockKindTemplate::Table {
+                        table: Some(table), ..
+                    } => Some(table),
+                    _ => None,
+                },
+            )
+            .collect();
+
+        if tables.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<Uuid> = tables.iter().map(|table| table.id).collect();
+        let mut contents = TableDbPostgres::get_contents(conn, &ids).await?;
+        for table in &mut tables {
+            if let Some((columns, rows)) = contents.remove(&table.id) {
+                table.data = Some(TableDataTemplate {
+                    html: Some(table_to_html(&columns, &rows)),
+                    columns: Some(columns),
+                    rows: Some(rows),
+                });
+            }
         }
+
+        Ok(())
     }
 
     async fn create(tx: &mut PgTransaction<'_>, create: PageCreate) -> Result<Page> {
+        let block_ids: Vec<Uuid> = create
+            .blocks
+            .iter()
+            .map(|page_block| page_block.block.id)
+            .collect();
         let item_id = Uuid::new_v4();
+        Self::validate(tx, &item_id, &create.r#type, &block_ids).await?;
+        if create.recipe.is_some() && create.r#type != PageType::Recipe {
+            return Err(DbError::Validation(RECIPE_METADATA_MESSAGE.to_string()).into());
+        }
+
         let mut item: Page = sqlx::query_as(
             "
             INSERT INTO public.pages (id, type, name)
@@ -558,15 +1243,26 @@ impl PageDbPostgres<'_> {
             seq += 1;
         }
 
+        if let Some(recipe) = create.recipe {
+            let recipe = recipe.into();
+            Self::upsert_recipe(tx, &item_id, &recipe).await?;
+            item.data.recipe = Some(recipe);
+        }
+
+        PageRevisionDbPostgres::record(tx, &item_id).await?;
+
         Ok(item)
     }
 
-    async fn update_by_id(
+    pub async fn update_by_id(
         tx: &mut PgTransaction<'_>,
         id: &Uuid,
         update: PageUpdate,
     ) -> Result<Page> {
-        let mut item = Self::get_by_id(&mut **tx, id).await?;
+        let mut item = Self::get_by_id(tx, id).await?;
+
+        // Renames and metadata changes leave the blocks of the page as they are
+        let structure_changed = update.r#type.is_some() || update.blocks.is_some();
 
         if let Some(ty) = update.r#type {
             item.data.r#type = ty;
@@ -575,6 +1271,77 @@ impl PageDbPostgres<'_> {
             item.data.name = name;
         }
 
+        match update.recipe.as_ref() {
+            Some(Some(update)) => {
+                let recipe = item.data.recipe.get_or_insert_with(Default::default);
+
+                if let Some(prep_time) = update.prep_time.as_ref() {
+                    recipe.prep_time = prep_time.copied();
+                }
+                if let Some(cook_time) = update.cook_time.as_ref() {
+                    recipe.cook_time = cook_time.copied();
+                }
+                if let Some(total_time) = update.total_time.as_ref() {
+                    recipe.total_time = total_time.copied();
+                }
+                if let Some(servings) = update.servings.as_ref() {
+                    recipe.servings = servings.copied();
+                }
+                if let Some(servings_unit) = update.servings_unit.as_ref() {
+                    recipe.servings_unit = servings_unit.cloned();
+                }
+                if let Some(difficulty) = update.difficulty.as_ref() {
+                    recipe.difficulty = difficulty.copied();
+                }
+                if let Some(cuisine) = update.cuisine.as_ref() {
+                    recipe.cuisine = cuisine.cloned();
+                }
+                if let Some(source_url) = update.source_url.as_ref() {
+                    recipe.source_url = source_url.cloned();
+                }
+                if let Some(source_author) = update.source_author.as_ref() {
+                    recipe.source_author = source_author.cloned();
+                }
+            }
+            Some(None) => item.data.recipe = None,
+            None => {}
+        }
+        if item.data.recipe.is_some() && item.data.r#type != PageType::Recipe {
+            return Err(DbError::Validation(RECIPE_METADATA_MESSAGE.to_string()).into());
+        }
+
+        match &item.data.recipe {
+            Some(recipe) => Self::upsert_recipe(tx, id, recipe).await?,
+            None => {
+                sqlx::query(
+                    "
+                    DELETE FROM public.recipe_metadata
+                    WHERE page_id = $1
+                    ",
+                )
+                .bind(id)
+                .execute(&mut **tx)
+                .await?;
+            }
+        }
+
+        // Either type or blocks might have changed, so the result as a whole is validated
+        if structure_changed {
+            let block_ids: Vec<Uuid> = match &update.blocks {
+                Some(blocks) => blocks
+                    .iter()
+                    .filter_map(|page_block| page_block.block.as_ref().map(|block| block.id))
+                    .collect(),
+                None => item
+                    .data
+                    .blocks
+                    .iter()
+                    .map(|page_block| page_block.block.id)
+                    .collect(),
+            };
+            Self::validate(tx, id, &item.data.r#type, &block_ids).await?;
+        }
+
         let row = sqlx::query(
             "
             UPDATE public.pages
@@ -593,47 +1360,622 @@ impl PageDbPostgres<'_> {
 
         item.ts_updated = row.get("ts_updated");
 
-        let mut updated_blocks = Vec::new();
-
         if let Some(blocks) = update.blocks {
-            for page_block in &item.data.blocks {
-                sqlx::query(
-                    "
-                    DELETE FROM public.page_blocks
-                    WHERE id = $1
-                    ",
-                )
-                .bind(page_block.link_id)
-                .execute(&mut **tx)
-                .await?;
-            }
+            let mut existing = std::mem::take(&mut item.data.blocks);
+            let mut links = Vec::new();
 
-            let mut seq = 0;
             for page_block in blocks {
                 if let Some(block) = page_block.block {
-                    let link_id = Uuid::new_v4();
-                    let _ = sqlx::query(
-                        "
-                        INSERT INTO public.page_blocks (id, page_id, block_id, sequence_number)
-                        VALUES ($1, $2, $3, $4)
-                        ",
-                    )
-                    .bind(link_id)
-                    .bind(id)
-                    .bind(block.id)
-                    .bind(seq)
-                    .execute(&mut **tx)
-                    .await?;
-
-                    updated_blocks.push(PageBlockTemplate { link_id, block });
-
-                    seq += 1;
+                    // Blocks that stay on the page keep their link
+                    let link_id = match existing
+                        .iter()
+                        .position(|existing| existing.block.id == block.id)
+                    {
+                        Some(index) => existing.remove(index).link_id,
+                        None => Self::insert_link(tx, id, &block.id, links.len()).await?,
+                    };
+
+                    links.push(link_id);
+                    item.data.blocks.push(PageBlockTemplate { link_id, block });
                 }
             }
 
-            item.data.blocks = updated_blocks;
+            for page_block in existing {
+                Self::delete_link(tx, &page_block.link_id).await?;
+            }
+
+            Self::write_block_order(tx, &links).await?;
         }
 
+        PageRevisionDbPostgres::record(tx, id).await?;
+
         Ok(item)
     }
+
+    async fn insert_block(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        insert: PageBlockInsert,
+    ) -> Result<PageBlock> {
+        let (ty, mut links) = Self::lock_blocks(tx, id).await?;
+
+        let position = insert.position.unwrap_or(links.len());
+        if position > links.len() {
+            return Err(DbError::Validation(BLOCK_POSITION_MESSAGE.to_string()).into());
+        }
+
+        let block_id = match insert.block {
+            PageBlockSource::Existing { block_id } => block_id,
+            PageBlockSource::Inline { kind } => Self::create_inline_block(tx, kind).await?,
+        };
+
+        let mut block_ids: Vec<Uuid> = links.iter().map(|(_, block_id)| *block_id).collect();
+        block_ids.insert(position, block_id);
+        Self::validate(tx, id, &ty, &block_ids).await?;
+
+        let link_id = Self::insert_link(tx, id, &block_id, position).await?;
+        links.insert(position, (link_id, block_id));
+
+        Self::finish_block_operation(tx, id, &links).await?;
+
+        // The block is returned the way it appears on the page
+        let item = Self::get_by_id(tx, id).await?;
+        match item
+            .data
+            .blocks
+            .into_iter()
+            .find(|page_block| page_block.link_id == link_id)
+        {
+            Some(page_block) => Ok(page_block),
+            None => Err(DbError::NotFound.into()),
+        }
+    }
+
+    async fn create_inline_block(
+        tx: &mut PgTransaction<'_>,
+        kind: PageBlockInline,
+    ) -> Result<Uuid> {
+        let kind = match kind {
+            PageBlockInline::Markdown { markdown } => {
+                let markdown = MarkdownDbPostgres::create(
+                    tx,
+                    MarkdownCreate {
+                        markdown,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+
+                BlockKindTemplate::Markdown {
+                    link_id: (),
+                    markdown: MarkdownReference {
+                        id: markdown.id,
+                        ..Default::default()
+                    },
+                }
+            }
+            PageBlockInline::IngredientCollection { ingredients } => {
+                let collection =
+                    IngredientCollectionDbPostgres::create(tx, Default::default()).await?;
+                for ingredient in ingredients {
+                    IngredientDbPostgres::create(tx, &collection.id, ingredient).await?;
+                }
+
+                BlockKindTemplate::IngredientCollection {
+                    link_id: (),
+                    ingredient_collection: IngredientCollectionReference {
+                        id: collection.id,
+                        ..Default::default()
+                    },
+                }
+            }
+        };
+
+        let block = BlockDbPostgres::create(tx, BlockDataTemplate { kind }).await?;
+
+        Ok(block.id)
+    }
+
+    async fn move_block(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        link_id: &Uuid,
+        block_move: PageBlockMove,
+    ) -> Result<Page> {
+        let (_, mut links) = Self::lock_blocks(tx, id).await?;
+
+        let index = match links.iter().position(|(link, _)| link == link_id) {
+            Some(index) => index,
+            None => return Err(DbError::NotFound.into()),
+        };
+        if block_move.position >= links.len() {
+            return Err(DbError::Validation(BLOCK_POSITION_MESSAGE.to_string()).into());
+        }
+
+        let link = links.remove(index);
+        links.insert(block_move.position, link);
+
+        Self::finish_block_operation(tx, id, &links).await?;
+
+        Self::get_by_id(tx, id).await
+    }
+
+    async fn remove_block(tx: &mut PgTransaction<'_>, id: &Uuid, link_id: &Uuid) -> Result<Page> {
+        let (ty, mut links) = Self::lock_blocks(tx, id).await?;
+
+        let index = match links.iter().position(|(link, _)| link == link_id) {
+            Some(index) => index,
+            None => return Err(DbError::NotFound.into()),
+        };
+        links.remove(index);
+
+        // Removing a block may leave the page without a block its type requires
+        let block_ids: Vec<Uuid> = links.iter().map(|(_, block_id)| *block_id).collect();
+        Self::validate(tx, id, &ty, &block_ids).await?;
+
+        Self::delete_link(tx, link_id).await?;
+
+        Self::finish_block_operation(tx, id, &links).await?;
+
+        Self::get_by_id(tx, id).await
+    }
+
+    // Locks the page so that concurrent block operations are applied one after the other
+    async fn lock_blocks(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+    ) -> Result<(PageType, Vec<(Uuid, Uuid)>)> {
+        let ty = match sqlx::query(
+            "
+            SELECT type
+            FROM public.pages
+            WHERE id = $1
+            FOR UPDATE
+            ",
+        )
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?
+        {
+            Some(row) => row.get("type"),
+            None => return Err(DbError::NotFound.into()),
+        };
+
+        let links = sqlx::query(
+            "
+            SELECT id, block_id
+            FROM public.page_blocks
+            WHERE page_id = $1
+            ORDER BY sequence_number
+            ",
+        )
+        .bind(id)
+        .fetch_all(&mut **tx)
+        .await?
+        .iter()
+        .map(|row| (row.get("id"), row.get("block_id")))
+        .collect();
+
+        Ok((ty, links))
+    }
+
+    async fn finish_block_operation(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        links: &[(Uuid, Uuid)],
+    ) -> Result<()> {
+        let link_ids: Vec<Uuid> = links.iter().map(|(link_id, _)| *link_id).collect();
+        Self::write_block_order(tx, &link_ids).await?;
+
+        sqlx::query(
+            "
+            UPDATE public.pages
+            SET ts_updated = NOW()
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+        PageRevisionDbPostgres::record(tx, id).await
+    }
+
+    async fn insert_link(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        block_id: &Uuid,
+        seq: usize,
+    ) -> Result<Uuid> {
+        let link_id = Uuid::new_v4();
+
+        sqlx::query(
+            "
+            INSERT INTO public.page_blocks (id, page_id, block_id, sequence_number)
+            VALUES ($1, $2, $3, $4)
+            ",
+        )
+        .bind(link_id)
+        .bind(id)
+        .bind(block_id)
+        .bind(seq as i32)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(link_id)
+    }
+
+    async fn delete_link(tx: &mut PgTransaction<'_>, link_id: &Uuid) -> Result<()> {
+        sqlx::query(
+            "
+            DELETE FROM public.page_blocks
+            WHERE id = $1
+            ",
+        )
+        .bind(link_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    // Renumbers the links of a page in the given order, leaving links already in place untouched
+    async fn write_block_order(tx: &mut PgTransaction<'_>, link_ids: &[Uuid]) -> Result<()> {
+        sqlx::query(
+            "
+            UPDATE public.page_blocks
+            SET sequence_number = ordered.seq - 1
+            FROM UNNEST($1::UUID[]) WITH ORDINALITY AS ordered (id, seq)
+            WHERE
+                page_blocks.id = ordered.id AND
+                page_blocks.sequence_number <> ordered.seq - 1
+            ",
+        )
+        .bind(link_ids)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn validate_pages_with_block(conn: &mut PgConnection, block_id: &Uuid) -> Result<()> {
+        let page_ids = PageRevisionDbPostgres::pages_with_block(conn, block_id).await?;
+
+        Self::validate_pages(conn, &page_ids).await
+    }
+
+    // Pages left without any block are validated as well
+    pub async fn validate_pages(conn: &mut PgConnection, page_ids: &[Uuid]) -> Result<()> {
+        let rows = sqlx::query(
+            "
+            SELECT
+                pages.id,
+                pages.type,
+                COALESCE(
+                    ARRAY_AGG(page_blocks.block_id ORDER BY page_blocks.sequence_number)
+                        FILTER (WHERE page_blocks.block_id IS NOT NULL),
+                    '{}'
+                ) AS block_ids
+            FROM public.pages
+                LEFT JOIN public.page_blocks
+                    ON pages.id = page_blocks.page_id
+            WHERE pages.id = ANY($1)
+            GROUP BY pages.id
+            ",
+        )
+        .bind(page_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for row in rows {
+            let block_ids: Vec<Uuid> = row.get("block_ids");
+            Self::validate(&mut *conn, &row.get("id"), &row.get("type"), &block_ids).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn validate(
+        conn: &mut PgConnection,
+        id: &Uuid,
+        ty: &PageType,
+        block_ids: &[Uuid],
+    ) -> Result<()> {
+        let rows = sqlx::query(
+            "
+            SELECT
+                id,
+                ingredient_collection_block_id IS NOT NULL AS is_ingredient_collection,
+                markdown_block_id IS NOT NULL AS is_markdown,
+                steps_block_id IS NOT NULL AS is_steps,
+                image_block_id IS NOT NULL AS is_image,
+                page_reference_block_id IS NOT NULL AS is_page_reference,
+                checklist_block_id IS NOT NULL AS is_checklist,
+                table_block_id IS NOT NULL AS is_table
+            FROM public.blocks
+            WHERE id = ANY($1)
+            ",
+        )
+        .bind(block_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let mut kinds = Vec::new();
+        for block_id in block_ids {
+            let row = match rows
+                .iter()
+                .find(|row| row.get::<Uuid, _>("id") == *block_id)
+            {
+                Some(row) => row,
+                None => return Err((DbError::NotFound).into()),
+            };
+
+            if row.get("is_ingredient_collection") {
+                kinds.push(PageBlockKind::IngredientCollection);
+            } else if row.get("is_markdown") {
+                kinds.push(PageBlockKind::Markdown);
+            } else if row.get("is_steps") {
+                kinds.push(PageBlockKind::Steps);
+            } else if row.get("is_image") {
+                kinds.push(PageBlockKind::Image);
+            } else if row.get("is_page_reference") {
+                kinds.push(PageBlockKind::PageReference);
+            } else if row.get("is_checklist") {
+                kinds.push(PageBlockKind::Checklist);
+            } else if row.get("is_table") {
+                kinds.push(PageBlockKind::Table);
+            }
+        }
+
+        ty.validate(&kinds)?;
+
+        // Steps may only reference ingredients of collections on the same page
+        let foreign = sqlx::query(
+            "
+            SELECT step_ingredients.ingredient_id
+            FROM public.blocks
+                JOIN public.steps
+                    ON blocks.steps_block_id = steps.steps_block_id
+                JOIN public.step_ingredients
+                    ON steps.id = step_ingredients.step_id
+            WHERE
+                blocks.id = ANY($1) AND
+                step_ingredients.ingredient_id NOT IN (
+                    SELECT ingredients.id
+                    FROM public.blocks
+                        JOIN public.ingredient_collection_blocks
+                            ON blocks.ingredient_collection_block_id = ingredient_collection_blocks.id
+                        JOIN public.ingredients
+                            ON ingredient_collection_blocks.ingredient_collection_id =
+                                ingredients.ingredient_collection_id
+                    WHERE blocks.id = ANY($1)
+                )
+            LIMIT 1
+            ",
+        )
+        .bind(block_ids)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        if foreign.is_some() {
+            return Err(DbError::Validation(
+                "steps may only reference ingredients of the same page".to_string(),
+            )
+            .into());
+        }
+
+        // Pages reachable through the new references must not lead back to this page. The
+        // current blocks of this page are not followed, as they are about to be replaced.
+        let cyclic: bool = sqlx::query(
+            "
+            WITH RECURSIVE reachable (page_id) AS (
+                    SELECT page_reference_blocks.page_id
+                    FROM public.blocks
+                        JOIN public.page_reference_blocks
+                            ON blocks.page_reference_block_id = page_reference_blocks.id
+                    WHERE blocks.id = ANY($1)
+                UNION
+                    SELECT page_reference_blocks.page_id
+                    FROM reachable
+                        JOIN public.page_blocks
+                            ON reachable.page_id = page_blocks.page_id
+                        JOIN public.blocks
+                            ON page_blocks.block_id = blocks.id
+                        JOIN public.page_reference_blocks
+                            ON blocks.page_reference_block_id = page_reference_blocks.id
+                    WHERE reachable.page_id <> $2
+            )
+            SELECT EXISTS (
+                SELECT 1
+                FROM reachable
+                WHERE page_id = $2
+            ) AS cyclic
+            ",
+        )
+        .bind(block_ids)
+        .bind(id)
+        .fetch_one(&mut *conn)
+        .await?
+        .get("cyclic");
+
+        if cyclic {
+            return Err(DbError::Validation(
+                "page references may not lead back to the page itself".to_string(),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    // Replaces page references with the full referenced pages, down to the given depth
+    fn expand<'a>(
+        conn: &'a mut PgConnection,
+        blocks: Vec<&'a mut BlockReference>,
+        depth: u32,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            if depth == 0 {
+                return Ok(());
+            }
+
+            for block in blocks {
+                let Some(BlockKindTemplate::PageReference {
+                    page: Some(page), ..
+                }) = block.data.as_mut().and_then(|data| data.kind.as_mut())
+                else {
+                    continue;
+                };
+
+                let mut expanded: PageReference = Self::get_by_id(conn, &page.id).await?.into();
+                let nested = expanded
+                    .data
+                    .iter_mut()
+                    .flat_map(|data| data.blocks.iter_mut().flatten())
+                    .filter_map(|page_block| page_block.block.as_mut())
+                    .collect();
+                Self::expand(&mut *conn, nested, depth - 1).await?;
+
+                *page = expanded;
+            }
+
+            Ok(())
+        })
+    }
+
+    async fn add_to_list(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        add: PageAddToList,
+    ) -> Result<Vec<ListItem>> {
+        // Makes sure both exist before anything is added
+        Self::get_by_id(tx, id).await?;
+        if sqlx::query(
+            "
+            SELECT id
+            FROM public.lists
+            WHERE id = $1
+            ",
+        )
+        .bind(add.list_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .is_none()
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        // Ingredients of the page itself come first, followed by those of referenced pages
+        let rows = sqlx::query(
+            "
+            WITH RECURSIVE included (page_id, depth) AS (
+                    SELECT $1::UUID, 0
+                UNION
+                    SELECT page_reference_blocks.page_id, included.depth + 1
+                    FROM included
+                        JOIN public.page_blocks
+                            ON included.page_id = page_blocks.page_id
+                        JOIN public.blocks
+                            ON page_blocks.block_id = blocks.id
+                        JOIN public.page_reference_blocks
+                            ON blocks.page_reference_block_id = page_reference_blocks.id
+                    WHERE included.depth < $2
+            )
+            SELECT ingredients.id
+            FROM included
+                JOIN public.page_blocks
+                    ON included.page_id = page_blocks.page_id
+                JOIN public.blocks
+                    ON page_blocks.block_id = blocks.id
+                JOIN public.ingredient_collection_blocks
+                    ON blocks.ingredient_collection_block_id = ingredient_collection_blocks.id
+                JOIN public.ingredients
+                    ON ingredient_collection_blocks.ingredient_collection_id =
+                        ingredients.ingredient_collection_id
+                JOIN public.products
+                    ON ingredients.product_id = products.id
+            ORDER BY included.depth, page_blocks.sequence_number, products.name
+            ",
+        )
+        .bind(id)
+        .bind(MAX_REFERENCE_DEPTH as i32)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let mut seen = HashSet::new();
+        let mut created = Vec::new();
+
+        for row in rows {
+            let ingredient_id: Uuid = row.get("id");
+            if !seen.insert(ingredient_id) {
+                continue;
+            }
+
+            created.push(
+                ListItemDbPostgres::create(
+                    tx,
+                    &add.list_id,
+                    ListItemCreate {
+                        checked: false,
+                        quantity: None,
+                        unit: None,
+                        note: None,
+                        kind: ListItemKindTemplate::Ingredient {
+                            link_id: (),
+                            ingredient: IngredientReference {
+                                id: ingredient_id,
+                                ..Default::default()
+                            },
+                        },
+                        list_reference: None,
+                        suggestions: None,
+                    },
+                )
+                .await?,
+            );
+        }
+
+        Ok(created)
+    }
+
+    async fn upsert_recipe(
+        tx: &mut PgTransaction<'_>,
+        page_id: &Uuid,
+        recipe: &RecipeMetadataTemplate<Query>,
+    ) -> Result<()> {
+        sqlx::query(
+            "
+            INSERT INTO public.recipe_metadata (
+                page_id, prep_time, cook_time, total_time, servings, servings_unit,
+                difficulty, cuisine, source_url, source_author
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (page_id) DO UPDATE
+            SET prep_time = EXCLUDED.prep_time,
+                cook_time = EXCLUDED.cook_time,
+                total_time = EXCLUDED.total_time,
+                servings = EXCLUDED.servings,
+                servings_unit = EXCLUDED.servings_unit,
+                difficulty = EXCLUDED.difficulty,
+                cuisine = EXCLUDED.cuisine,
+                source_url = EXCLUDED.source_url,
+                source_author = EXCLUDED.source_author,
+                ts_updated = NOW()
+            ",
+        )
+        .bind(page_id)
+        .bind(recipe.prep_time)
+        .bind(recipe.cook_time)
+        .bind(recipe.total_time)
+        .bind(recipe.servings)
+        .bind(&recipe.servings_unit)
+        .bind(recipe.difficulty)
+        .bind(&recipe.cuisine)
+        .bind(&recipe.source_url)
+        .bind(&recipe.source_author)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
 }
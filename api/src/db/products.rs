@@ -4,12 +4,15 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{stream::Peekable, Stream, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
+use sqlx::{
+    postgres::PgRow, prelude::FromRow, PgConnection, PgExecutor, PgPool, PgTransaction, Row,
+};
 use uuid::Uuid;
 
 use crate::utilities::{
     modifier::{Create, Modifier, Query, Reference, Update},
     request::collection::Pagination,
+    search::escape_like,
 };
 
 use super::{
@@ -27,6 +30,12 @@ pub trait ProductDb {
     async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
 }
 
+// Minimal similarity for a product to be suggested as a match
+pub const SUGGESTION_THRESHOLD: f32 = 0.3;
+
+// Maximum number of suggested products
+pub const SUGGESTION_LIMIT: i64 = 5;
+
 pub type Product = ProductTemplate<Query>;
 pub type ProductCreate = ProductDataTemplate<Create>;
 pub type ProductUpdate = ProductDataTemplate<Update>;
@@ -50,6 +59,9 @@ pub struct ProductDataTemplate<M: Modifier> {
     #[serde(skip_deserializing)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub list_item_references: Option<Vec<ListItemReference>>,
+    #[serde(skip_deserializing)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_score: Option<f32>,
 }
 
 #[derive(Default, Debug, Deserialize, Clone)]
@@ -67,6 +79,7 @@ impl FromRow<'_, PgRow> for Product {
             data: ProductDataTemplate {
                 name: row.get("name"),
                 list_item_references: None,
+                match_score: None,
             },
         })
     }
@@ -108,6 +121,7 @@ impl Product {
             ts_updated: first.get("ts_updated"),
             data: ProductDataTemplate {
                 name: first.get("name"),
+                match_score: None,
                 list_item_references: Some({
                     let mut items = Vec::new();
 
@@ -179,7 +193,7 @@ impl ProductDb for ProductDbPostgres<'_> {
                 lists.id AS list_id,
                 lists.name AS list_name,
 
-                similarity($1, products.name) AS match_score
+                similarity(normalize_text($1), normalize_text(products.name)) AS match_score
 
             FROM public.products
                 LEFT JOIN public.product_list_items
@@ -189,6 +203,11 @@ impl ProductDb for ProductDbPostgres<'_> {
                 LEFT JOIN public.lists
                     ON list_items.list_id = lists.id
 
+            WHERE
+                normalize_text(products.name) LIKE '%' || normalize_text($3) || '%' OR
+                normalize_text(products.name) % normalize_text($1) OR
+                $1 IS NULL
+
             ORDER BY
                 match_score DESC,
                 products.name,
@@ -197,8 +216,9 @@ impl ProductDb for ProductDbPostgres<'_> {
             LIMIT $2
             ",
         )
-        .bind(params.name)
+        .bind(params.name.as_deref())
         .bind(params.take)
+        .bind(params.name.as_deref().map(escape_like))
         .fetch(&mut *tx);
 
         let products = Product::collect_products(stream).await?;
@@ -362,4 +382,54 @@ impl ProductDbPostgres<'_> {
 
         Ok(item)
     }
+
+    // Products ranked by trigram similarity of their name, like in product search, ignoring
+    // accents and case. The indexed similarity operator narrows them down first, so thresholds
+    // below its default of 0.3 act like 0.3
+    pub async fn get_similar(
+        conn: &mut PgConnection,
+        name: &str,
+        threshold: f32,
+        take: i64,
+    ) -> Result<Vec<ProductReference>> {
+        let rows = sqlx::query(
+            "
+            SELECT
+                products.id,
+                products.name,
+
+                similarity(normalize_text($1), normalize_text(products.name)) AS match_score
+
+            FROM public.products
+
+            WHERE
+                normalize_text(products.name) % normalize_text($1) AND
+                similarity(normalize_text($1), normalize_text(products.name)) >= $2
+
+            ORDER BY
+                match_score DESC,
+                products.name
+
+            LIMIT $3
+            ",
+        )
+        .bind(name)
+        .bind(threshold)
+        .bind(take)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ProductReference {
+                id: row.get("id"),
+                data: Some(ProductDataTemplate {
+                    name: Some(row.get("name")),
+                    match_score: Some(row.get("match_score")),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .collect())
+    }
 }
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::utilities::search::{snippet_to_html, HIGHLIGHT_START, HIGHLIGHT_STOP};
+
+use super::{
+    pages::{PageDataTemplate, PageReference},
+    DbError,
+};
+
+#[trait_variant::make(Send)]
+pub trait SearchDb {
+    async fn get_multiple(&mut self, params: SearchParams) -> Result<Vec<SearchResult>>;
+}
+
+// Number of pages found when no limit is given
+const DEFAULT_TAKE: i64 = 20;
+
+// Larger limits are capped to this number of pages
+const MAX_TAKE: i64 = 100;
+
+// Matches are grouped by page, matches of the page itself come without a block
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub page: PageReference,
+    pub rank: f32,
+    pub block_ids: Vec<Uuid>,
+    // HTML with matches wrapped in `mark` elements
+    pub snippets: Vec<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct SearchParams {
+    pub q: String,
+    pub take: Option<i64>,
+}
+
+impl SearchParams {
+    fn take(&self) -> Result<i64> {
+        match self.take {
+            Some(take) if take < 0 => {
+                Err(DbError::Validation("take may not be negative".to_string()).into())
+            }
+            Some(take) => Ok(take.min(MAX_TAKE)),
+            None => Ok(DEFAULT_TAKE),
+        }
+    }
+}
+
+pub struct SearchDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> SearchDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl SearchDb for SearchDbPostgres<'_> {
+    async fn get_multiple(&mut self, params: SearchParams) -> Result<Vec<SearchResult>> {
+        let take = params.take()?;
+        let mut conn = self.pool.acquire().await?;
+
+        let rows = sqlx::query(
+            "
+            WITH search AS (
+                SELECT
+                    websearch_to_tsquery('public.simple_unaccent', $1) AS query,
+                    FORMAT('StartSel=%s, StopSel=%s', $2::TEXT, $3::TEXT) AS options
+            ),
+            matches (page_id, block_id, rank, snippet) AS (
+                    -- Names count double, as they say most about a page
+                    SELECT
+                        pages.id,
+                        NULL::UUID,
+                        ts_rank(pages.search_vector, search.query) * 2,
+                        ts_headline(
+                            'public.simple_unaccent', pages.name, search.query,
+                            search.options || ', HighlightAll=true'
+                        )
+                    FROM public.pages, search
+                    WHERE pages.search_vector @@ search.query
+                UNION ALL
+                    SELECT
+                        page_blocks.page_id,
+                        blocks.id,
+                        ts_rank(markdown.search_vector, search.query),
+                        ts_headline(
+                            'public.simple_unaccent', markdown.markdown, search.query,
+                            search.options || ', MaxFragments=2, MaxWords=20, MinWords=5'
+                        )
+                    FROM public.markdown
+                        JOIN public.markdown_blocks
+                            ON markdown.id = markdown_blocks.markdown_id
+                        JOIN public.blocks
+                            ON markdown_blocks.id = blocks.markdown_block_id
+                        JOIN public.page_blocks
+                            ON blocks.id = page_blocks.block_id,
+                        search
+                    WHERE markdown.search_vector @@ search.query
+                UNION ALL
+                    SELECT
+                        page_blocks.page_id,
+                        blocks.id,
+                        ts_rank(products.search_vector, search.query),
+                        ts_headline(
+                            'public.simple_unaccent', products.name, search.query,
+                            search.options || ', HighlightAll=true'
+                        )
+                    FROM public.products
+                        JOIN public.ingredients
+                            ON products.id = ingredients.product_id
+                        JOIN public.ingredient_collection_blocks
+                            ON ingredients.ingredient_collection_id =
+                                ingredient_collection_blocks.ingredient_collection_id
+                        JOIN public.blocks
+                            ON ingredient_collection_blocks.id =
+                                blocks.ingredient_collection_block_id
+                        JOIN public.page_blocks
+                            ON blocks.id = page_blocks.block_id,
+                        search
+                    WHERE products.search_vector @@ search.query
+            )
+            SELECT
+                pages.id,
+                pages.type,
+                pages.name,
+                SUM(matches.rank)::REAL AS rank,
+                ARRAY_REMOVE(ARRAY_AGG(DISTINCT matches.block_id), NULL) AS block_ids,
+                ARRAY_AGG(matches.snippet ORDER BY matches.rank DESC) AS snippets
+            FROM matches
+                JOIN public.pages
+                    ON matches.page_id = pages.id
+            GROUP BY pages.id
+            ORDER BY
+                rank DESC,
+                pages.name
+            LIMIT $4
+            ",
+        )
+        .bind(&params.q)
+        .bind(HIGHLIGHT_START.to_string())
+        .bind(HIGHLIGHT_STOP.to_string())
+        .bind(take)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let mut snippets: Vec<String> = Vec::new();
+                // The same ingredient may be used several times on a page
+                for snippet in row.get::<Vec<String>, _>("snippets") {
+                    let snippet = snippet_to_html(&snippet);
+                    if !snippets.contains(&snippet) {
+                        snippets.push(snippet);
+                    }
+                }
+
+                SearchResult {
+                    page: PageReference {
+                        id: row.get("id"),
+                        data: Some(PageDataTemplate {
+                            r#type: Some(row.get("type")),
+                            name: Some(row.get("name")),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                    rank: row.get("rank"),
+                    block_ids: row.get("block_ids"),
+                    snippets,
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,347 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{TryFutureExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
+use uuid::Uuid;
+
+use crate::utilities::modifier::{Create, Modifier, Query, Update};
+
+use super::{
+    list_items::{
+        ListItemCreate, ListItemDbPostgres, ListItemKindTemplate, ListItemUpdate,
+        TemporaryListItemDataTemplate, TemporaryListItemTemplate,
+    },
+    lists::{ListCreate, ListDbPostgres},
+    products::{ProductDataTemplate, ProductReference},
+    DbError,
+};
+
+// Name of the list created when the shopping list is used without any list
+const DEFAULT_LIST_NAME: &str = "Shopping list";
+
+// The shopping list is a view onto the items of the default list, which is the
+// oldest list that is not archived. Being in the cart maps onto being checked.
+#[trait_variant::make(Send)]
+pub trait ShoppingListDb {
+    async fn get_multiple(&mut self) -> Result<Vec<ShoppingListItem>>;
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<ShoppingListItem>;
+    async fn create_multiple(
+        &mut self,
+        items: Vec<ShoppingListItemCreate>,
+    ) -> Result<Vec<ShoppingListItem>>;
+    async fn update_by_id(
+        &mut self,
+        id: &Uuid,
+        item: ShoppingListItemUpdate,
+    ) -> Result<ShoppingListItem>;
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
+}
+
+pub type ShoppingListItem = ShoppingListItemTemplate<Query>;
+pub type ShoppingListItemCreate = ShoppingListItemDataTemplate<Create>;
+pub type ShoppingListItemUpdate = ShoppingListItemDataTemplate<Update>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ShoppingListItemTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(rename = "created")]
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(rename = "updated")]
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<ShoppingListItemDataTemplate<M>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShoppingListItemDataTemplate<M: Modifier> {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub in_cart: M::Data<bool>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub source: M::Data<ListItemKindTemplate<M>>,
+}
+
+impl From<ShoppingListItemCreate> for ListItemCreate {
+    fn from(value: ShoppingListItemCreate) -> Self {
+        Self {
+            checked: value.in_cart,
+            quantity: None,
+            unit: None,
+            note: None,
+            kind: value.source,
+            list_reference: None,
+            suggestions: None,
+        }
+    }
+}
+
+impl From<ShoppingListItemUpdate> for ListItemUpdate {
+    fn from(value: ShoppingListItemUpdate) -> Self {
+        Self {
+            checked: value.in_cart,
+            quantity: Default::default(),
+            unit: Default::default(),
+            note: Default::default(),
+            kind: value.source,
+            list_reference: None,
+            suggestions: None,
+        }
+    }
+}
+
+impl FromRow<'_, PgRow> for ShoppingListItem {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            ts_created: row.get("ts_created"),
+            ts_updated: row.get("ts_updated"),
+            data: ShoppingListItemDataTemplate {
+                in_cart: row.get("checked"),
+                // Ingredient items are shown as the product of their ingredient
+                source: if let Some(id) = row.get("temporary_list_item_id") {
+                    ListItemKindTemplate::Temporary {
+                        link_id: id,
+                        temporary: TemporaryListItemTemplate {
+                            data: TemporaryListItemDataTemplate {
+                                name: row.get("temporary_list_item_name"),
+                            },
+                        },
+                    }
+                } else {
+                    ListItemKindTemplate::Product {
+                        link_id: row
+                            .get::<Option<Uuid>, _>("product_list_item_id")
+                            .unwrap_or_else(|| row.get("ingredient_list_item_id")),
+                        product: ProductReference {
+                            id: row.get("product_id"),
+                            data: Some(ProductDataTemplate {
+                                name: Some(row.get("product_name")),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                    }
+                },
+            },
+        })
+    }
+}
+
+pub struct ShoppingListDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> ShoppingListDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ShoppingListDb for ShoppingListDbPostgres<'_> {
+    async fn get_multiple(&mut self) -> Result<Vec<ShoppingListItem>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_multiple(&mut *conn, None).await
+    }
+
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<ShoppingListItem> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut *conn, id).await
+    }
+
+    async fn create_multiple(
+        &mut self,
+        items: Vec<ShoppingListItemCreate>,
+    ) -> Result<Vec<ShoppingListItem>> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = match Self::create_multiple(&mut tx, items).await {
+            Ok(created) => created,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn update_by_id(
+        &mut self,
+        id: &Uuid,
+        item: ShoppingListItemUpdate,
+    ) -> Result<ShoppingListItem> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = match Self::update_by_id(&mut tx, id, item).await {
+            Ok(updated) => updated,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        // Relying on SQL trigger to delete corresponding list item types
+        if sqlx::query(
+            "
+            DELETE FROM public.list_items
+            WHERE id = $1 AND list_id = (
+                SELECT id
+                FROM public.lists
+                WHERE NOT archived
+                ORDER BY ts_created, id
+                LIMIT 1
+            )
+            ",
+        )
+        .bind(id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        Ok(())
+    }
+}
+
+impl ShoppingListDbPostgres<'_> {
+    async fn get_default_list_id(tx: &mut PgTransaction<'_>) -> Result<Uuid> {
+        let existing = sqlx::query(
+            "
+            SELECT id
+            FROM public.lists
+            WHERE NOT archived
+            ORDER BY ts_created, id
+            LIMIT 1
+            ",
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        if let Some(row) = existing {
+            return Ok(row.get("id"));
+        }
+
+        let created = ListDbPostgres::create(
+            tx,
+            ListCreate {
+                name: DEFAULT_LIST_NAME.to_string(),
+                archived: false,
+                item_refs: Default::default(),
+            },
+        )
+        .await?;
+
+        Ok(created.id)
+    }
+
+    async fn get_multiple<'c, E>(executor: E, id: Option<&Uuid>) -> Result<Vec<ShoppingListItem>>
+    where
+        E: PgExecutor<'c>,
+    {
+        sqlx::query_as(
+            "
+            SELECT
+                list_items.id,
+                list_items.ts_created,
+                list_items.ts_updated,
+                list_items.checked,
+                ingredient_list_items.id AS ingredient_list_item_id,
+                product_list_items.id AS product_list_item_id,
+                COALESCE(products.id, ingredient_products.id) AS product_id,
+                COALESCE(products.name, ingredient_products.name) AS product_name,
+                temporary_list_items.id AS temporary_list_item_id,
+                temporary_list_items.name AS temporary_list_item_name
+
+            FROM public.list_items
+                LEFT JOIN public.ingredient_list_items
+                    ON list_items.ingredient_list_item_id = ingredient_list_items.id
+                LEFT JOIN public.ingredients
+                    ON ingredient_list_items.ingredient_id = ingredients.id
+                LEFT JOIN public.products AS ingredient_products
+                    ON ingredients.product_id = ingredient_products.id
+
+                LEFT JOIN public.product_list_items
+                    ON list_items.product_list_item_id = product_list_items.id
+                LEFT JOIN public.products
+                    ON product_list_items.product_id = products.id
+
+                LEFT JOIN public.temporary_list_items
+                    ON list_items.temporary_list_item_id = temporary_list_items.id
+
+            WHERE
+                list_items.list_id = (
+                    SELECT id
+                    FROM public.lists
+                    WHERE NOT archived
+                    ORDER BY ts_created, id
+                    LIMIT 1
+                ) AND
+                (list_items.id = $1 OR $1 IS NULL)
+            ORDER BY
+                COALESCE(products.name, ingredient_products.name, temporary_list_items.name),
+                list_items.id
+            ",
+        )
+        .bind(id)
+        .fetch(executor)
+        .try_collect()
+        .map_err(|error| error.into())
+        .await
+    }
+
+    async fn get_by_id<'c, E>(executor: E, id: &Uuid) -> Result<ShoppingListItem>
+    where
+        E: PgExecutor<'c>,
+    {
+        match Self::get_multiple(executor, Some(id)).await?.pop() {
+            Some(item) => Ok(item),
+            None => Err((DbError::NotFound).into()),
+        }
+    }
+
+    async fn create_multiple(
+        tx: &mut PgTransaction<'_>,
+        items: Vec<ShoppingListItemCreate>,
+    ) -> Result<Vec<ShoppingListItem>> {
+        let list_id = Self::get_default_list_id(tx).await?;
+        let mut created = Vec::new();
+
+        for item in items {
+            let item = ListItemDbPostgres::create(tx, &list_id, item.into()).await?;
+            created.push(Self::get_by_id(&mut **tx, &item.id).await?);
+        }
+
+        Ok(created)
+    }
+
+    async fn update_by_id(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        update: ShoppingListItemUpdate,
+    ) -> Result<ShoppingListItem> {
+        let list_id = Self::get_default_list_id(tx).await?;
+
+        ListItemDbPostgres::update_by_id(tx, &list_id, id, update.into()).await?;
+
+        Self::get_by_id(&mut **tx, id).await
+    }
+}
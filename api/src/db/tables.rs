@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{TryFutureExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgConnection, PgPool, PgTransaction, Row};
+use uuid::Uuid;
+
+use crate::utilities::{
+    modifier::{Create, Modifier, Query, Reference, Update},
+    tables::{self, table_to_html, Cell, Column, TableError},
+};
+
+use super::{page_revisions::PageRevisionDbPostgres, DbError};
+
+#[trait_variant::make(Send)]
+pub trait TableDb {
+    async fn get_multiple(&mut self) -> Result<Vec<Table>>;
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<Table>;
+    async fn create_multiple(&mut self, items: Vec<TableCreate>) -> Result<Vec<Table>>;
+    async fn update_by_id(&mut self, id: &Uuid, item: TableUpdate) -> Result<Table>;
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()>;
+    async fn insert_row(&mut self, id: &Uuid, insert: TableRowInsert) -> Result<Table>;
+    async fn delete_row(&mut self, id: &Uuid, index: usize) -> Result<Table>;
+    async fn insert_column(&mut self, id: &Uuid, insert: TableColumnInsert) -> Result<Table>;
+    async fn delete_column(&mut self, id: &Uuid, index: usize) -> Result<Table>;
+}
+
+pub type Table = TableTemplate<Query>;
+pub type TableCreate = TableDataTemplate<Create>;
+pub type TableUpdate = TableDataTemplate<Update>;
+pub type TableReference = TableTemplate<Reference>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct TableTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<TableDataTemplate<M>>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct TableDataTemplate<M: Modifier> {
+    // Headers and alignment of the columns, making up the header row
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub columns: M::Data<Vec<Column>>,
+    // Every row holds exactly one cell per column
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub rows: M::Data<Vec<tables::Row>>,
+    #[serde(skip_deserializing)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct TableRowInsert {
+    // Appended when missing
+    #[serde(default)]
+    pub position: Option<usize>,
+    // Empty when missing
+    #[serde(default)]
+    pub cells: Option<tables::Row>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct TableColumnInsert {
+    // Appended when missing
+    #[serde(default)]
+    pub position: Option<usize>,
+    #[serde(flatten)]
+    pub column: Column,
+    // One cell per row, empty when missing
+    #[serde(default)]
+    pub cells: Option<Vec<Option<Cell>>>,
+}
+
+impl FromRow<'_, PgRow> for Table {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            ts_created: row.get("ts_created"),
+            ts_updated: row.get("ts_updated"),
+            // Contents are collected separately
+            data: TableDataTemplate::default(),
+        })
+    }
+}
+
+impl From<TableError> for DbError {
+    fn from(error: TableError) -> Self {
+        DbError::Validation(error.to_string())
+    }
+}
+
+pub struct TableDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> TableDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TableDb for TableDbPostgres<'_> {
+    async fn get_multiple(&mut self) -> Result<Vec<Table>> {
+        let mut conn = self.pool.acquire().await?;
+
+        let mut items: Vec<Table> = sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated
+            FROM public.tables
+            ORDER BY ts_created, id
+            ",
+        )
+        .fetch(&mut *conn)
+        .try_collect()
+        .await?;
+
+        let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+        let mut contents = Self::get_contents(&mut conn, &ids).await?;
+        for item in &mut items {
+            if let Some((columns, rows)) = contents.remove(&item.id) {
+                item.data.html = Some(table_to_html(&columns, &rows));
+                item.data.columns = columns;
+                item.data.rows = rows;
+            }
+        }
+
+        Ok(items)
+    }
+
+    async fn get_by_id(&mut self, id: &Uuid) -> Result<Table> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut conn, id).await
+    }
+
+    async fn create_multiple(&mut self, items: Vec<TableCreate>) -> Result<Vec<Table>> {
+        let mut tx = self.pool.begin().await?;
+        let mut created = Vec::new();
+
+        for item in items {
+            match Self::create(&mut tx, item).await {
+                Ok(item) => created.push(item),
+                Err(error) => {
+                    tx.rollback().await?;
+                    return Err(error);
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn update_by_id(&mut self, id: &Uuid, item: TableUpdate) -> Result<Table> {
+        self.modify(id, |columns, rows| {
+            if let Some(update) = item.columns {
+                *columns = update;
+            }
+            if let Some(update) = item.rows {
+                *rows = update;
+            }
+
+            tables::validate(columns, rows)
+        })
+        .await
+    }
+
+    async fn delete_by_id(&mut self, id: &Uuid) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        if sqlx::query(
+            "
+            DELETE FROM public.tables
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        Ok(())
+    }
+
+    async fn insert_row(&mut self, id: &Uuid, insert: TableRowInsert) -> Result<Table> {
+        self.modify(id, |columns, rows| {
+            tables::insert_row(columns, rows, insert.position, insert.cells)
+        })
+        .await
+    }
+
+    async fn delete_row(&mut self, id: &Uuid, index: usize) -> Result<Table> {
+        self.modify(id, |_, rows| tables::delete_row(rows, index))
+            .await
+    }
+
+    async fn insert_column(&mut self, id: &Uuid, insert: TableColumnInsert) -> Result<Table> {
+        self.modify(id, |columns, rows| {
+            tables::insert_column(columns, rows, insert.position, insert.column, insert.cells)
+        })
+        .await
+    }
+
+    async fn delete_column(&mut self, id: &Uuid, index: usize) -> Result<Table> {
+        self.modify(id, |columns, rows| {
+            tables::delete_column(columns, rows, index)
+        })
+        .await
+    }
+}
+
+impl TableDbPostgres<'_> {
+    // Applies a change to the contents of a table and writes them back as a whole
+    async fn modify<F>(&mut self, id: &Uuid, change: F) -> Result<Table>
+    where
+        F: FnOnce(&mut Vec<Column>, &mut Vec<tables::Row>) -> Result<(), TableError> + Send,
+    {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = match Self::modify_contents(&mut tx, id, change).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn get_by_id(conn: &mut PgConnection, id: &Uuid) -> Result<Table> {
+        let mut item: Table = sqlx::query_as(
+            "
+            SELECT id, ts_created, ts_updated
+            FROM public.tables
+            WHERE id = $1
+            ",
+        )
+        .bind(id)
+        .fetch_one(&mut *conn)
+        .map_err(|error| match error {
+            sqlx::Error::RowNotFound => Into::<anyhow::Error>::into(DbError::NotFound),
+            _ => error.into(),
+        })
+        .await?;
+
+        if let Some((columns, rows)) = Self::get_contents(conn, &[item.id]).await?.remove(id) {
+            item.data.html = Some(table_to_html(&columns, &rows));
+            item.data.columns = columns;
+            item.data.rows = rows;
+        }
+
+        Ok(item)
+    }
+
+    pub async fn get_contents(
+        conn: &mut PgConnection,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, (Vec<Column>, Vec<tables::Row>)>> {
+        let mut contents: HashMap<Uuid, (Vec<Column>, Vec<tables::Row>)> = ids
+            .iter()
+            .map(|id| (*id, (Vec::new(), Vec::new())))
+            .collect();
+
+        let columns = sqlx::query(
+            "
+            SELECT table_id, header, alignment
+            FROM public.table_columns
+            WHERE table_id = ANY($1)
+            ORDER BY sequence_number
+            ",
+        )
+        .bind(ids)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for row in columns {
+            if let Some((columns, _)) = contents.get_mut(&row.get("table_id")) {
+                columns.push(Column {
+                    header: row.get("header"),
+                    alignment: row.get("alignment"),
+                });
+            }
+        }
+
+        // Rows without columns still show up, with no cells
+        let cells = sqlx::query(
+            "
+            SELECT
+                table_rows.table_id,
+                table_rows.id AS row_id,
+                table_columns.id AS column_id,
+                table_cells.text,
+                table_cells.number
+
+            FROM public.table_rows
+                LEFT JOIN public.table_columns
+                    ON table_rows.table_id = table_columns.table_id
+                LEFT JOIN public.table_cells
+                    ON table_rows.id = table_cells.row_id
+                    AND table_columns.id = table_cells.column_id
+
+            WHERE table_rows.table_id = ANY($1)
+            ORDER BY
+                table_rows.table_id,
+                table_rows.sequence_number,
+                table_rows.id,
+                table_columns.sequence_number
+            ",
+        )
+        .bind(ids)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let rows = tables::collect_rows(cells.iter().map(|row| {
+            let cell = row.get::<Option<Uuid>, _>("column_id").map(|_| {
+                match (row.get("text"), row.get("number")) {
+                    (Some(text), _) => Some(Cell::Text(text)),
+                    (_, Some(number)) => Some(Cell::Number(number)),
+                    _ => None,
+                }
+            });
+            (row.get("table_id"), row.get("row_id"), cell)
+        }));
+
+        for (table_id, table_rows) in rows {
+            if let Some((_, rows)) = contents.get_mut(&table_id) {
+                *rows = table_rows;
+            }
+        }
+
+        Ok(contents)
+    }
+
+    async fn create(tx: &mut PgTransaction<'_>, create: TableCreate) -> Result<Table> {
+        tables::validate(&create.columns, &create.rows).map_err(DbError::from)?;
+
+        let mut item: Table = sqlx::query_as(
+            "
+            INSERT INTO public.tables (id)
+            VALUES ($1)
+            RETURNING id, ts_created, ts_updated
+            ",
+        )
+        .bind(Uuid::new_v4())
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Self::write_contents(tx, &item.id, &create.columns, &create.rows).await?;
+
+        item.data = TableDataTemplate {
+            html: Some(table_to_html(&create.columns, &create.rows)),
+            columns: create.columns,
+            rows: create.rows,
+        };
+
+        Ok(item)
+    }
+
+    async fn modify_contents<F>(tx: &mut PgTransaction<'_>, id: &Uuid, change: F) -> Result<Table>
+    where
+        F: FnOnce(&mut Vec<Column>, &mut Vec<tables::Row>) -> Result<(), TableError> + Send,
+    {
+        let mut item = Self::get_by_id(tx, id).await?;
+
+        change(&mut item.data.columns, &mut item.data.rows).map_err(DbError::from)?;
+
+        Self::write_contents(tx, id, &item.data.columns, &item.data.rows).await?;
+
+        let row = sqlx::query(
+            "
+            UPDATE public.tables
+            SET ts_updated = NOW()
+            WHERE id = $1
+            RETURNING ts_updated
+            ",
+        )
+        .bind(id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        item.ts_updated = row.get("ts_updated");
+        item.data.html = Some(table_to_html(&item.data.columns, &item.data.rows));
+
+        PageRevisionDbPostgres::record_with_table(tx, id).await?;
+
+        Ok(item)
+    }
+
+    // Contents are replaced as a whole, which keeps sequence numbers free of gaps
+    pub async fn write_contents(
+        tx: &mut PgTransaction<'_>,
+        id: &Uuid,
+        columns: &[Column],
+        rows: &[tables::Row],
+    ) -> Result<()> {
+        sqlx::query(
+            "
+            DELETE FROM public.table_columns
+            WHERE table_id = $1
+            ",
+        )
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            "
+            DELETE FROM public.table_rows
+            WHERE table_id = $1
+            ",
+        )
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+        let mut column_ids = Vec::new();
+        for (seq, column) in columns.iter().enumerate() {
+            let column_id = Uuid::new_v4();
+            sqlx::query(
+                "
+                INSERT INTO public.table_columns (id, table_id, sequence_number, header, alignment)
+                VALUES ($1, $2, $3, $4, $5)
+                ",
+            )
+            .bind(column_id)
+            .bind(id)
+            .bind(seq as i32)
+            .bind(&column.header)
+            .bind(column.alignment)
+            .execute(&mut **tx)
+            .await?;
+
+            column_ids.push(column_id);
+        }
+
+        for (seq, cells) in rows.iter().enumerate() {
+            let row_id = Uuid::new_v4();
+            sqlx::query(
+                "
+                INSERT INTO public.table_rows (id, table_id, sequence_number)
+                VALUES ($1, $2, $3)
+                ",
+            )
+            .bind(row_id)
+            .bind(id)
+            .bind(seq as i32)
+            .execute(&mut **tx)
+            .await?;
+
+            for (column_id, cell) in column_ids.iter().zip(cells) {
+                let (text, number) = match cell {
+                    Some(Cell::Text(text)) => (Some(text), None),
+                    Some(Cell::Number(number)) => (None, Some(*number)),
+                    None => continue,
+                };
+
+                sqlx::query(
+                    "
+                    INSERT INTO public.table_cells (row_id, column_id, text, number)
+                    VALUES ($1, $2, $3, $4)
+                    ",
+                )
+                .bind(row_id)
+                .bind(column_id)
+                .bind(text)
+                .bind(number)
+                .execute(&mut **tx)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
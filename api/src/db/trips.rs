@@ -0,0 +1,536 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgRow, prelude::FromRow, PgExecutor, PgPool, PgTransaction, Row};
+use uuid::Uuid;
+
+use crate::utilities::modifier::{Modifier, Query};
+
+use super::{
+    products::{ProductDataTemplate, ProductReference},
+    DbError,
+};
+
+// Number of trips after which older observations of a position start to fade
+const POSITION_MEMORY: i32 = 10;
+
+#[trait_variant::make(Send)]
+pub trait TripDb {
+    async fn get_multiple(&mut self, list_id: &Uuid) -> Result<Vec<Trip>>;
+    async fn get_by_id(&mut self, list_id: &Uuid, id: &Uuid) -> Result<Trip>;
+    async fn complete(&mut self, list_id: &Uuid) -> Result<Trip>;
+    async fn delete_by_id(&mut self, list_id: &Uuid, id: &Uuid) -> Result<()>;
+    async fn get_statistics(&mut self, params: StatisticsParams)
+        -> Result<Vec<PurchaseStatistics>>;
+    async fn get_due(&mut self, list_id: &Uuid) -> Result<Vec<PurchaseStatistics>>;
+}
+
+pub type Trip = TripTemplate<Query>;
+pub type TripItem = TripItemTemplate<Query>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct TripTemplate<M: Modifier> {
+    pub id: M::Key<Uuid>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_created: M::Meta<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "M::skip_meta")]
+    pub ts_updated: M::Meta<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub data: M::Data<TripDataTemplate<M>>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct TripDataTemplate<M: Modifier> {
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub ts_completed: M::Data<DateTime<Utc>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub items: M::Data<Vec<TripItemTemplate<M>>>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct TripItemTemplate<M: Modifier> {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub product: M::Nullable<ProductReference>,
+    #[serde(skip_serializing_if = "M::skip_data")]
+    pub name: M::Data<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub quantity: M::Nullable<f64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "M::skip_nullable")]
+    pub unit: M::Nullable<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+pub struct StatisticsParams {
+    pub list_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PurchaseStatistics {
+    pub product: ProductReference,
+    pub purchase_count: i64,
+    pub ts_first_purchased: DateTime<Utc>,
+    pub ts_last_purchased: DateTime<Utc>,
+    // Average number of days between purchases
+    pub average_interval: Option<f64>,
+    pub ts_due: Option<DateTime<Utc>>,
+}
+
+impl FromRow<'_, PgRow> for PurchaseStatistics {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            product: ProductReference {
+                id: row.get("product_id"),
+                data: Some(ProductDataTemplate {
+                    name: Some(row.get("product_name")),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            purchase_count: row.get("purchase_count"),
+            ts_first_purchased: row.get("ts_first_purchased"),
+            ts_last_purchased: row.get("ts_last_purchased"),
+            average_interval: row.get("average_interval"),
+            ts_due: row.get("ts_due"),
+        })
+    }
+}
+
+impl FromRow<'_, PgRow> for Trip {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            ts_created: row.get("ts_created"),
+            ts_updated: row.get("ts_updated"),
+            data: TripDataTemplate {
+                ts_completed: row.get("ts_completed"),
+                items: Vec::new(),
+            },
+        })
+    }
+}
+
+impl FromRow<'_, PgRow> for TripItem {
+    fn from_row(row: &'_ PgRow) -> std::result::Result<Self, sqlx::Error> {
+        Ok(Self {
+            product: row
+                .get::<Option<Uuid>, _>("product_id")
+                .map(|id| ProductReference {
+                    id,
+                    data: Some(ProductDataTemplate {
+                        name: Some(row.get("product_name")),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+            name: row.get("item_name"),
+            quantity: row.get("item_quantity"),
+            unit: row.get("item_unit"),
+        })
+    }
+}
+
+impl Trip {
+    async fn try_items_from_stream(
+        rows: &mut (impl Stream<Item = Result<PgRow, sqlx::Error>> + Unpin),
+    ) -> Result<Vec<Self>> {
+        let mut items: Vec<Trip> = Vec::new();
+
+        while let Some(row) = rows.try_next().await? {
+            if items.last().map(|item| item.id) != Some(row.get("id")) {
+                items.push(Trip::from_row(&row)?);
+            }
+
+            if row.get::<Option<Uuid>, _>("item_id").is_some() {
+                if let Some(item) = items.last_mut() {
+                    item.data.items.push(TripItem::from_row(&row)?);
+                }
+            }
+        }
+
+        Ok(items)
+    }
+}
+
+pub struct TripDbPostgres<'a> {
+    pool: &'a PgPool,
+}
+
+impl<'a> TripDbPostgres<'a> {
+    pub fn new(pool: &'a PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl TripDb for TripDbPostgres<'_> {
+    async fn get_multiple(&mut self, list_id: &Uuid) -> Result<Vec<Trip>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_multiple(&mut *conn, list_id, None).await
+    }
+
+    async fn get_by_id(&mut self, list_id: &Uuid, id: &Uuid) -> Result<Trip> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_by_id(&mut *conn, list_id, id).await
+    }
+
+    async fn complete(&mut self, list_id: &Uuid) -> Result<Trip> {
+        let mut tx = self.pool.begin().await?;
+
+        let completed = match Self::complete(&mut tx, list_id).await {
+            Ok(item) => item,
+            Err(error) => {
+                tx.rollback().await?;
+                return Err(error);
+            }
+        };
+
+        tx.commit().await?;
+
+        Ok(completed)
+    }
+
+    async fn delete_by_id(&mut self, list_id: &Uuid, id: &Uuid) -> Result<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        // Relying on cascaded delete of corresponding trip items
+        if sqlx::query(
+            "
+            DELETE FROM public.trips
+            WHERE list_id = $1 AND id = $2
+            ",
+        )
+        .bind(list_id)
+        .bind(id)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected()
+            == 0
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        Ok(())
+    }
+
+    async fn get_statistics(
+        &mut self,
+        params: StatisticsParams,
+    ) -> Result<Vec<PurchaseStatistics>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_statistics(&mut *conn, params.list_id.as_ref(), false).await
+    }
+
+    async fn get_due(&mut self, list_id: &Uuid) -> Result<Vec<PurchaseStatistics>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Self::get_statistics(&mut *conn, Some(list_id), true).await
+    }
+}
+
+impl TripDbPostgres<'_> {
+    async fn get_multiple<'c, E>(
+        executor: E,
+        list_id: &Uuid,
+        id: Option<&Uuid>,
+    ) -> Result<Vec<Trip>>
+    where
+        E: PgExecutor<'c>,
+    {
+        let mut stream = sqlx::query(
+            "
+            SELECT
+                trips.id,
+                trips.ts_created,
+                trips.ts_updated,
+                trips.ts_completed,
+                trip_items.id AS item_id,
+                trip_items.name AS item_name,
+                trip_items.quantity AS item_quantity,
+                trip_items.unit AS item_unit,
+                products.id AS product_id,
+                products.name AS product_name
+
+            FROM public.trips
+                LEFT JOIN public.trip_items
+                    ON trips.id = trip_items.trip_id
+                LEFT JOIN public.products
+                    ON trip_items.product_id = products.id
+
+            WHERE
+                trips.list_id = $1 AND
+                (trips.id = $2 OR $2 IS NULL)
+
+            ORDER BY
+                trips.ts_completed DESC,
+                trips.id,
+                trip_items.name
+            ",
+        )
+        .bind(list_id)
+        .bind(id)
+        .fetch(executor);
+
+        Trip::try_items_from_stream(&mut stream).await
+    }
+
+    async fn get_by_id<'c, E>(executor: E, list_id: &Uuid, id: &Uuid) -> Result<Trip>
+    where
+        E: PgExecutor<'c>,
+    {
+        match Self::get_multiple(executor, list_id, Some(id)).await?.pop() {
+            Some(item) => Ok(item),
+            None => Err((DbError::NotFound).into()),
+        }
+    }
+
+    async fn complete(tx: &mut PgTransaction<'_>, list_id: &Uuid) -> Result<Trip> {
+        if sqlx::query(
+            "
+            SELECT id
+            FROM public.lists
+            WHERE id = $1
+            ",
+        )
+        .bind(list_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .is_none()
+        {
+            return Err((DbError::NotFound).into());
+        }
+
+        let checked = sqlx::query(
+            "
+            SELECT
+                COALESCE(products.id, ingredient_products.id) AS product_id,
+                COALESCE(
+                    products.name,
+                    ingredient_products.name,
+                    temporary_list_items.name
+                ) AS name,
+                list_items.quantity,
+                list_items.unit
+
+            FROM public.list_items
+                LEFT JOIN public.ingredient_list_items
+                    ON list_items.ingredient_list_item_id = ingredient_list_items.id
+                LEFT JOIN public.ingredients
+                    ON ingredient_list_items.ingredient_id = ingredients.id
+                LEFT JOIN public.products AS ingredient_products
+                    ON ingredients.product_id = ingredient_products.id
+
+                LEFT JOIN public.product_list_items
+                    ON list_items.product_list_item_id = product_list_items.id
+                LEFT JOIN public.products
+                    ON product_list_items.product_id = products.id
+
+                LEFT JOIN public.temporary_list_items
+                    ON list_items.temporary_list_item_id = temporary_list_items.id
+
+            WHERE list_items.list_id = $1 AND list_items.checked
+            ",
+        )
+        .bind(list_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        // There is nothing to complete without any checked items
+        if checked.is_empty() {
+            return Err((DbError::InvalidOperation).into());
+        }
+
+        let trip_id = Uuid::new_v4();
+        sqlx::query(
+            "
+            INSERT INTO public.trips (id, list_id)
+            VALUES ($1, $2)
+            ",
+        )
+        .bind(trip_id)
+        .bind(list_id)
+        .execute(&mut **tx)
+        .await?;
+
+        for row in checked {
+            sqlx::query(
+                "
+                INSERT INTO public.trip_items (id, trip_id, product_id, name, quantity, unit)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ",
+            )
+            .bind(Uuid::new_v4())
+            .bind(trip_id)
+            .bind(row.get::<Option<Uuid>, _>("product_id"))
+            .bind(row.get::<String, _>("name"))
+            .bind(row.get::<Option<f64>, _>("quantity"))
+            .bind(row.get::<Option<String>, _>("unit"))
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Self::learn_positions(tx, list_id).await?;
+
+        // Relying on SQL trigger to delete corresponding list item types
+        sqlx::query(
+            "
+            DELETE FROM public.list_items
+            WHERE list_id = $1 AND checked
+            ",
+        )
+        .bind(list_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Self::get_by_id(&mut **tx, list_id, &trip_id).await
+    }
+
+    // Moves the learned store position of every checked product towards its
+    // position within this trip, so the store order improves with every trip
+    async fn learn_positions(tx: &mut PgTransaction<'_>, list_id: &Uuid) -> Result<()> {
+        sqlx::query(
+            "
+            WITH checked AS (
+                SELECT
+                    COALESCE(product_list_items.product_id, ingredients.product_id) AS product_id,
+                    MIN(list_items.ts_checked) AS ts_checked
+                FROM public.list_items
+                    LEFT JOIN public.ingredient_list_items
+                        ON list_items.ingredient_list_item_id = ingredient_list_items.id
+                    LEFT JOIN public.ingredients
+                        ON ingredient_list_items.ingredient_id = ingredients.id
+                    LEFT JOIN public.product_list_items
+                        ON list_items.product_list_item_id = product_list_items.id
+                WHERE
+                    list_items.list_id = $1 AND
+                    list_items.checked AND
+                    list_items.ts_checked IS NOT NULL AND
+                    COALESCE(product_list_items.product_id, ingredients.product_id) IS NOT NULL
+                GROUP BY 1
+            ),
+            ranked AS (
+                SELECT
+                    product_id,
+                    (ROW_NUMBER() OVER (ORDER BY ts_checked) - 1)::DOUBLE PRECISION
+                        / NULLIF(COUNT(*) OVER () - 1, 0) AS position
+                FROM checked
+            )
+
+            INSERT INTO public.product_positions (product_id, position)
+            SELECT product_id, position
+            FROM ranked
+            WHERE position IS NOT NULL
+            ON CONFLICT (product_id) DO UPDATE
+            SET position = product_positions.position
+                    + (EXCLUDED.position - product_positions.position)
+                    / LEAST(product_positions.observations + 1, $2),
+                observations = product_positions.observations + 1,
+                ts_updated = NOW()
+            ",
+        )
+        .bind(list_id)
+        .bind(POSITION_MEMORY)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    // Due products are overdue by their average interval and not yet on the list
+    async fn get_statistics<'c, E>(
+        executor: E,
+        list_id: Option<&Uuid>,
+        due: bool,
+    ) -> Result<Vec<PurchaseStatistics>>
+    where
+        E: PgExecutor<'c>,
+    {
+        let items = sqlx::query_as(
+            "
+            WITH purchases AS (
+                SELECT DISTINCT
+                    trip_items.product_id,
+                    trips.id AS trip_id,
+                    trips.ts_completed
+                FROM public.trip_items
+                    INNER JOIN public.trips
+                        ON trip_items.trip_id = trips.id
+                WHERE
+                    trip_items.product_id IS NOT NULL AND
+                    (trips.list_id = $1 OR $1 IS NULL)
+            ),
+            intervals AS (
+                SELECT
+                    product_id,
+                    ts_completed,
+                    ts_completed - LAG(ts_completed) OVER (
+                        PARTITION BY product_id
+                        ORDER BY ts_completed
+                    ) AS interval
+                FROM purchases
+            )
+
+            SELECT
+                products.id AS product_id,
+                products.name AS product_name,
+                COUNT(*) AS purchase_count,
+                MIN(intervals.ts_completed) AS ts_first_purchased,
+                MAX(intervals.ts_completed) AS ts_last_purchased,
+                (EXTRACT(EPOCH FROM AVG(intervals.interval)) / 86400)::DOUBLE PRECISION
+                    AS average_interval,
+                MAX(intervals.ts_completed) + AVG(intervals.interval) AS ts_due
+
+            FROM intervals
+                INNER JOIN public.products
+                    ON intervals.product_id = products.id
+
+            WHERE
+                NOT $2 OR (
+                    NOT EXISTS (
+                        SELECT
+                        FROM public.list_items
+                            INNER JOIN public.product_list_items
+                                ON list_items.product_list_item_id = product_list_items.id
+                        WHERE
+                            list_items.list_id = $1 AND
+                            product_list_items.product_id = products.id
+                    ) AND
+                    NOT EXISTS (
+                        SELECT
+                        FROM public.list_items
+                            INNER JOIN public.ingredient_list_items
+                                ON list_items.ingredient_list_item_id = ingredient_list_items.id
+                            INNER JOIN public.ingredients
+                                ON ingredient_list_items.ingredient_id = ingredients.id
+                        WHERE
+                            list_items.list_id = $1 AND
+                            ingredients.product_id = products.id
+                    )
+                )
+
+            GROUP BY products.id, products.name
+
+            HAVING
+                NOT $2 OR
+                MAX(intervals.ts_completed) + AVG(intervals.interval) <= CURRENT_TIMESTAMP
+
+            ORDER BY
+                CASE WHEN $2 THEN MAX(intervals.ts_completed) + AVG(intervals.interval) END,
+                purchase_count DESC,
+                products.name
+            ",
+        )
+        .bind(list_id)
+        .bind(due)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(items)
+    }
+}
@@ -1,7 +1,9 @@
 use crate::db::{Db, DbPostgres};
+use crate::storage::{Storage, StorageLocal};
 
 pub struct AppState {
     pub db: AppDb,
+    pub storage: AppStorage,
 }
 
 impl AppState {
@@ -10,8 +12,18 @@ impl AppState {
             AppDb::Postgres(db) => db,
         }
     }
+
+    pub fn storage(&self) -> &impl Storage {
+        match &self.storage {
+            AppStorage::Local(storage) => storage,
+        }
+    }
 }
 
 pub enum AppDb {
     Postgres(DbPostgres),
 }
+
+pub enum AppStorage {
+    Local(StorageLocal),
+}
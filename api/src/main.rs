@@ -5,8 +5,9 @@ use axum::{
     Router,
 };
 use db::{Db, DbPostgres};
-use global::{AppDb, AppState};
+use global::{AppDb, AppState, AppStorage};
 use sqlx::postgres::PgPoolOptions;
+use storage::StorageLocal;
 use tower::ServiceBuilder;
 use tower_http::set_header::SetResponseHeaderLayer;
 use tracing::level_filters::LevelFilter;
@@ -15,6 +16,7 @@ use tracing_subscriber::{filter::EnvFilter, fmt, layer::SubscriberExt, util::Sub
 mod api;
 mod db;
 mod global;
+mod storage;
 mod utilities;
 
 #[tokio::main]
@@ -49,6 +51,9 @@ async fn main() {
 
     let app_state = Arc::new(AppState {
         db: AppDb::Postgres(DbPostgres::new(db_pool)),
+        storage: AppStorage::Local(StorageLocal::new(
+            env::var("STORAGE_PATH").unwrap_or("storage".into()),
+        )),
     });
 
     tracing::info!("migrating database");
@@ -0,0 +1,86 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+
+#[trait_variant::make(Send)]
+pub trait Storage {
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<()>;
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+pub struct StorageLocal {
+    root: PathBuf,
+}
+
+impl StorageLocal {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        let key = Path::new(key);
+
+        // Keys must stay inside of the storage directory
+        if !key
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err((StorageError::InvalidKey).into());
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+impl Storage for StorageLocal {
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(bytes),
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                Err((StorageError::NotFound).into())
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.path(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        Ok(tokio::fs::write(path, bytes).await?)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                Err((StorageError::NotFound).into())
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageError {
+    NotFound,
+    InvalidKey,
+}
+
+impl Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StorageError::NotFound => write!(f, "object could not be found"),
+            StorageError::InvalidKey => write!(f, "key is not a valid object key"),
+        }
+    }
+}
+
+impl Error for StorageError {}
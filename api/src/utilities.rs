@@ -1,7 +1,14 @@
+pub mod export;
 pub mod group_iter;
 pub mod group_stream;
+pub mod images;
 pub mod markdown;
+pub mod merge;
 pub mod modifier;
 pub mod pack;
 pub mod patch;
+pub mod quick_add;
 pub mod request;
+pub mod revisions;
+pub mod search;
+pub mod tables;
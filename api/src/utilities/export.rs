@@ -0,0 +1,215 @@
+use std::fmt::Write;
+
+use pulldown_cmark::Options;
+
+use super::markdown::markdown_to_html;
+
+pub struct ExportItem<'a> {
+    pub name: &'a str,
+    pub checked: bool,
+    pub quantity: Option<f64>,
+    pub unit: Option<&'a str>,
+    pub note: Option<&'a str>,
+}
+
+// Titled groups of items, in the order they are listed in the export
+pub type ExportGroups<'a> = [(&'a str, Vec<ExportItem<'a>>)];
+
+impl ExportItem<'_> {
+    fn label(&self) -> String {
+        let mut label = String::new();
+
+        if let Some(quantity) = self.quantity {
+            let _ = write!(label, "{} ", quantity);
+        }
+        if let Some(unit) = self.unit {
+            let _ = write!(label, "{} ", unit);
+        }
+
+        label.push_str(self.name);
+        label
+    }
+}
+
+pub fn to_text(name: &str, groups: &ExportGroups) -> String {
+    let mut text = format!("{}\n", name);
+
+    for (title, items) in groups {
+        let _ = write!(text, "\n{}\n", title);
+
+        for item in items {
+            let _ = write!(
+                text,
+                "[{}] {}",
+                if item.checked { "x" } else { " " },
+                item.label()
+            );
+            if let Some(note) = item.note {
+                let _ = write!(text, " ({})", note);
+            }
+            text.push('\n');
+        }
+    }
+
+    text
+}
+
+pub fn to_markdown(name: &str, groups: &ExportGroups) -> String {
+    let mut markdown = format!("# {}\n", escape_markdown(name));
+
+    for (title, items) in groups {
+        let _ = write!(markdown, "\n## {}\n\n", title);
+
+        for item in items {
+            let label = escape_markdown(&item.label());
+            if item.checked {
+                let _ = write!(markdown, "- ~~{}~~", label);
+            } else {
+                let _ = write!(markdown, "- {}", label);
+            }
+            if let Some(note) = item.note {
+                let _ = write!(markdown, " *({})*", escape_markdown(note));
+            }
+            markdown.push('\n');
+        }
+    }
+
+    markdown
+}
+
+pub fn to_html(name: &str, groups: &ExportGroups) -> String {
+    format!(
+        "<!DOCTYPE html>\n\
+        <html>\n\
+        <head>\n\
+        <meta charset=\"utf-8\">\n\
+        <title>{}</title>\n\
+        </head>\n\
+        <body>\n\
+        {}\
+        </body>\n\
+        </html>\n",
+        escape_html(name),
+        // Checked items are struck through in the Markdown export
+        markdown_to_html(&to_markdown(name, groups), Options::ENABLE_STRIKETHROUGH),
+    )
+}
+
+// Names are user input, so they should never be interpreted as markup
+fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for character in value.chars() {
+        if character.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+
+    escaped
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> Vec<(&'static str, Vec<ExportItem<'static>>)> {
+        vec![
+            (
+                "Products",
+                vec![
+                    ExportItem {
+                        name: "Milk",
+                        checked: true,
+                        quantity: Some(2.0),
+                        unit: None,
+                        note: None,
+                    },
+                    ExportItem {
+                        name: "Flour",
+                        checked: false,
+                        quantity: Some(1.5),
+                        unit: Some("kg"),
+                        note: Some("whole grain"),
+                    },
+                ],
+            ),
+            (
+                "Other",
+                vec![ExportItem {
+                    name: "<b>Cake</b>",
+                    checked: false,
+                    quantity: None,
+                    unit: None,
+                    note: None,
+                }],
+            ),
+        ]
+    }
+
+    #[test]
+    fn export_text() {
+        assert_eq!(
+            to_text("Weekly", &groups()),
+            "Weekly\n\
+            \n\
+            Products\n\
+            [x] 2 Milk\n\
+            [ ] 1.5 kg Flour (whole grain)\n\
+            \n\
+            Other\n\
+            [ ] <b>Cake</b>\n"
+        );
+    }
+
+    #[test]
+    fn export_markdown() {
+        assert_eq!(
+            to_markdown("Weekly #1", &groups()),
+            "# Weekly \\#1\n\
+            \n\
+            ## Products\n\
+            \n\
+            - ~~2 Milk~~\n\
+            - 1\\.5 kg Flour *(whole grain)*\n\
+            \n\
+            ## Other\n\
+            \n\
+            - \\<b\\>Cake\\<\\/b\\>\n"
+        );
+    }
+
+    #[test]
+    fn export_html() {
+        assert_eq!(
+            to_html("Weekly & more", &groups()),
+            "<!DOCTYPE html>\n\
+            <html>\n\
+            <head>\n\
+            <meta charset=\"utf-8\">\n\
+            <title>Weekly &amp; more</title>\n\
+            </head>\n\
+            <body>\n\
+            <h1>Weekly &amp; more</h1>\n\
+            <h2>Products</h2>\n\
+            <ul>\n\
+            <li><del>2 Milk</del></li>\n\
+            <li>1.5 kg Flour <em>(whole grain)</em></li>\n\
+            </ul>\n\
+            <h2>Other</h2>\n\
+            <ul>\n\
+            <li>&lt;b&gt;Cake&lt;/b&gt;</li>\n\
+            </ul>\n\
+            </body>\n\
+            </html>\n"
+        );
+    }
+}
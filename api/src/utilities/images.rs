@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Cursor;
+
+use image::{imageops::FilterType, ImageFormat};
+
+pub const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024;
+pub const THUMBNAIL_WIDTHS: &[u32] = &[320, 640, 1280];
+
+const FORMATS: &[(&str, ImageFormat)] = &[
+    ("image/jpeg", ImageFormat::Jpeg),
+    ("image/png", ImageFormat::Png),
+    ("image/gif", ImageFormat::Gif),
+    ("image/webp", ImageFormat::WebP),
+];
+
+pub struct ProcessedImage {
+    pub content_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+pub struct Thumbnail {
+    pub width: u32,
+    pub bytes: Vec<u8>,
+}
+
+// Photos stay JPEG, anything else is turned into PNG to keep transparency
+pub fn thumbnail_content_type(content_type: &str) -> &'static str {
+    match content_type {
+        "image/jpeg" => "image/jpeg",
+        _ => "image/png",
+    }
+}
+
+pub fn process(bytes: &[u8], content_type: Option<&str>) -> Result<ProcessedImage, ImageError> {
+    if bytes.len() > MAX_IMAGE_SIZE {
+        return Err(ImageError::TooLarge);
+    }
+
+    // The declared type has to match what the bytes actually are
+    let format = image::guess_format(bytes).map_err(|_| ImageError::UnsupportedType)?;
+    let content_type = match FORMATS.iter().find(|(_, known)| *known == format) {
+        Some((known, _)) if content_type.is_none_or(|declared| declared == *known) => *known,
+        _ => return Err(ImageError::UnsupportedType),
+    };
+
+    let image =
+        image::load_from_memory_with_format(bytes, format).map_err(|_| ImageError::Invalid)?;
+    let thumbnail_format = match thumbnail_content_type(content_type) {
+        "image/jpeg" => ImageFormat::Jpeg,
+        _ => ImageFormat::Png,
+    };
+
+    let mut thumbnails = Vec::new();
+    for &width in THUMBNAIL_WIDTHS {
+        // Images are never scaled up
+        if width >= image.width() {
+            break;
+        }
+
+        let height =
+            (u64::from(image.height()) * u64::from(width) / u64::from(image.width())).max(1) as u32;
+        let mut bytes = Vec::new();
+        image
+            .resize_exact(width, height, FilterType::Triangle)
+            .write_to(&mut Cursor::new(&mut bytes), thumbnail_format)
+            .map_err(|_| ImageError::Invalid)?;
+
+        thumbnails.push(Thumbnail { width, bytes });
+    }
+
+    Ok(ProcessedImage {
+        content_type,
+        width: image.width(),
+        height: image.height(),
+        thumbnails,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageError {
+    TooLarge,
+    UnsupportedType,
+    Invalid,
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ImageError::TooLarge => write!(f, "image exceeds {} bytes", MAX_IMAGE_SIZE),
+            ImageError::UnsupportedType => write!(f, "image type is not supported"),
+            ImageError::Invalid => write!(f, "image could not be decoded"),
+        }
+    }
+}
+
+impl Error for ImageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use image::DynamicImage;
+
+    fn encode(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::new_rgb8(width, height)
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn process_thumbnails() {
+        let processed = process(&encode(800, 400, ImageFormat::Png), Some("image/png")).unwrap();
+
+        assert_eq!(processed.content_type, "image/png");
+        assert_eq!((processed.width, processed.height), (800, 400));
+        assert_eq!(
+            processed
+                .thumbnails
+                .iter()
+                .map(|thumbnail| thumbnail.width)
+                .collect::<Vec<_>>(),
+            vec![320, 640]
+        );
+
+        let thumbnail = image::load_from_memory(&processed.thumbnails[0].bytes).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (320, 160));
+    }
+
+    #[test]
+    fn process_small() {
+        let processed = process(&encode(100, 100, ImageFormat::Jpeg), None).unwrap();
+
+        assert_eq!(processed.content_type, "image/jpeg");
+        assert!(processed.thumbnails.is_empty());
+    }
+
+    #[test]
+    fn process_rejects() {
+        let png = encode(10, 10, ImageFormat::Png);
+
+        assert_eq!(
+            process(&png, Some("image/jpeg")).err(),
+            Some(ImageError::UnsupportedType)
+        );
+        assert_eq!(
+            process(b"not an image", None).err(),
+            Some(ImageError::UnsupportedType)
+        );
+        assert_eq!(
+            process(&png[..png.len() / 2], None).err(),
+            Some(ImageError::Invalid)
+        );
+        assert_eq!(
+            process(&vec![0; MAX_IMAGE_SIZE + 1], None).err(),
+            Some(ImageError::TooLarge)
+        );
+    }
+}
@@ -1,10 +1,27 @@
-use pulldown_cmark::{html::push_html, Parser};
+use pulldown_cmark::{html::push_html, Options, Parser};
 
-pub fn markdown_to_html(markdown: &str) -> String {
-    let parser = Parser::new(markdown);
+pub fn markdown_to_html(markdown: &str, options: Options) -> String {
+    let parser = Parser::new_ext(markdown, options);
     let mut html = String::new();
 
     push_html(&mut html, parser);
 
     html
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strikethrough_only_when_enabled() {
+        assert_eq!(
+            markdown_to_html("~~milk~~", Options::empty()),
+            "<p>~~milk~~</p>\n"
+        );
+        assert_eq!(
+            markdown_to_html("~~milk~~", Options::ENABLE_STRIKETHROUGH),
+            "<p><del>milk</del></p>\n"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct MergeItem<K> {
+    pub id: K,
+    pub product_id: Option<K>,
+    pub checked: bool,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MergeStep<K> {
+    // An item of the target list absorbs items of the other list
+    Update {
+        id: K,
+        checked: bool,
+        quantity: Option<f64>,
+    },
+    // An item of the other list is copied over, along with the items it absorbed
+    Copy {
+        id: K,
+        checked: bool,
+        quantity: Option<f64>,
+    },
+}
+
+struct Merged<K> {
+    id: K,
+    checked: bool,
+    quantity: Option<f64>,
+    absorbed: bool,
+}
+
+// Items of the same product add up when they are in the same unit, all others are copied, so the
+// other list itself is never changed
+pub fn plan_merge<K: Copy + Eq + Hash>(
+    target: &[MergeItem<K>],
+    other: &[MergeItem<K>],
+) -> Vec<MergeStep<K>> {
+    let mut updates = Vec::new();
+    let mut copies = Vec::new();
+    let mut by_product: HashMap<(K, Option<&str>), (bool, usize)> = HashMap::new();
+
+    for item in target {
+        if let Some(product_id) = item.product_id {
+            by_product
+                .entry((product_id, item.unit.as_deref()))
+                .or_insert((false, updates.len()));
+        }
+        updates.push(Merged {
+            id: item.id,
+            checked: item.checked,
+            quantity: item.quantity,
+            absorbed: false,
+        });
+    }
+
+    for item in other {
+        let key = item
+            .product_id
+            .map(|product_id| (product_id, item.unit.as_deref()));
+
+        let existing = match key.and_then(|key| by_product.get(&key)) {
+            Some((false, index)) => Some(&mut updates[*index]),
+            Some((true, index)) => Some(&mut copies[*index]),
+            None => None,
+        };
+
+        match existing {
+            Some(existing) => {
+                existing.checked = existing.checked && item.checked;
+                existing.quantity = merge_quantities(existing.quantity, item.quantity);
+                existing.absorbed = true;
+            }
+            None => {
+                if let Some(key) = key {
+                    by_product.insert(key, (true, copies.len()));
+                }
+                copies.push(Merged {
+                    id: item.id,
+                    checked: item.checked,
+                    quantity: item.quantity,
+                    absorbed: false,
+                });
+            }
+        }
+    }
+
+    updates
+        .into_iter()
+        .filter(|merged| merged.absorbed)
+        .map(|merged| MergeStep::Update {
+            id: merged.id,
+            checked: merged.checked,
+            quantity: merged.quantity,
+        })
+        .chain(copies.into_iter().map(|merged| MergeStep::Copy {
+            id: merged.id,
+            checked: merged.checked,
+            quantity: merged.quantity,
+        }))
+        .collect()
+}
+
+// An item without a quantity counts as one of it once the other has a quantity
+fn merge_quantities(existing: Option<f64>, other: Option<f64>) -> Option<f64> {
+    match (existing, other) {
+        (None, None) => None,
+        (existing, other) => Some(existing.unwrap_or(1.0) + other.unwrap_or(1.0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        id: u32,
+        product_id: Option<u32>,
+        checked: bool,
+        quantity: Option<f64>,
+        unit: Option<&str>,
+    ) -> MergeItem<u32> {
+        MergeItem {
+            id,
+            product_id,
+            checked,
+            quantity,
+            unit: unit.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn merge_adds_up_same_product_and_unit() {
+        let target = [
+            item(1, Some(100), true, Some(2.0), None),
+            item(2, Some(200), false, Some(500.0), Some("g")),
+        ];
+        let other = [
+            item(3, Some(100), true, None, None),
+            item(4, Some(200), false, Some(1.0), Some("kg")),
+            item(5, Some(100), false, Some(3.0), None),
+        ];
+
+        assert_eq!(
+            plan_merge(&target, &other),
+            vec![
+                MergeStep::Update {
+                    id: 1,
+                    checked: false,
+                    quantity: Some(6.0),
+                },
+                MergeStep::Copy {
+                    id: 4,
+                    checked: false,
+                    quantity: Some(1.0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_copies_items_of_the_other_list() {
+        let target = [item(1, None, false, None, None)];
+        let other = [
+            item(2, None, false, None, None),
+            item(3, Some(100), true, None, None),
+            item(4, Some(100), true, Some(2.0), None),
+        ];
+
+        // Nothing of the other list is moved, so it stays as it was
+        assert_eq!(
+            plan_merge(&target, &other),
+            vec![
+                MergeStep::Copy {
+                    id: 2,
+                    checked: false,
+                    quantity: None,
+                },
+                MergeStep::Copy {
+                    id: 3,
+                    checked: true,
+                    quantity: Some(3.0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_without_quantities() {
+        assert_eq!(merge_quantities(None, None), None);
+        assert_eq!(merge_quantities(Some(2.0), None), Some(3.0));
+        assert_eq!(merge_quantities(None, Some(2.0)), Some(3.0));
+    }
+}
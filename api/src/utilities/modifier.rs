@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use super::patch::Patch;
+
 pub trait Modifier {
     type Key<T>;
     type Meta<T>;
     type Data<T>;
+    type Nullable<T>;
 
     fn skip_meta<T>(_: &Self::Meta<T>) -> bool {
         false
@@ -11,6 +14,9 @@ pub trait Modifier {
     fn skip_data<T>(_: &Self::Data<T>) -> bool {
         false
     }
+    fn skip_nullable<T>(_: &Self::Nullable<T>) -> bool {
+        false
+    }
 }
 
 #[derive(Default, Debug, Serialize)]
@@ -20,6 +26,7 @@ impl Modifier for Query {
     type Key<T> = T;
     type Meta<T> = T;
     type Data<T> = T;
+    type Nullable<T> = Option<T>;
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -29,6 +36,7 @@ impl Modifier for Create {
     type Key<T> = ();
     type Meta<T> = ();
     type Data<T> = T;
+    type Nullable<T> = Option<T>;
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -38,6 +46,7 @@ impl Modifier for Update {
     type Key<T> = ();
     type Meta<T> = ();
     type Data<T> = Option<T>;
+    type Nullable<T> = Patch<T>;
 }
 
 #[derive(Default, Debug, Serialize, Deserialize)]
@@ -47,6 +56,7 @@ impl Modifier for Reference {
     type Key<T> = T;
     type Meta<T> = Option<T>;
     type Data<T> = Option<T>;
+    type Nullable<T> = Option<T>;
 
     fn skip_meta<T>(value: &Self::Meta<T>) -> bool {
         value.is_none()
@@ -54,4 +64,7 @@ impl Modifier for Reference {
     fn skip_data<T>(value: &Self::Data<T>) -> bool {
         value.is_none()
     }
+    fn skip_nullable<T>(value: &Self::Nullable<T>) -> bool {
+        value.is_none()
+    }
 }
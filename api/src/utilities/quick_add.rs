@@ -0,0 +1,174 @@
+use serde::Serialize;
+
+const UNITS: &[&str] = &[
+    "mg", "g", "kg", "ml", "cl", "dl", "l", "tsp", "tbsp", "cup", "cups", "pc", "pcs", "pack",
+    "packs", "can", "cans", "bottle", "bottles",
+];
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Fragment {
+    pub text: String,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+}
+
+pub fn parse(input: &str) -> Vec<Fragment> {
+    input
+        .split([',', ';', '\n'])
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(parse_fragment)
+        .collect()
+}
+
+fn parse_fragment(text: &str) -> Fragment {
+    let (quantity, rest) = match split_quantity(text) {
+        Some((quantity, rest)) => (Some(quantity), rest),
+        None => (None, text),
+    };
+
+    // A unit is only expected directly after a quantity
+    let (unit, rest) = match quantity.and_then(|_| split_unit(rest)) {
+        Some((unit, rest)) => (Some(unit), rest),
+        None => (None, rest),
+    };
+
+    let name = rest.trim();
+
+    // Nothing left to name the item, as in "42" or "500g", so the whole text is the name
+    if name.is_empty() {
+        return Fragment {
+            text: text.to_string(),
+            name: text.to_string(),
+            quantity: None,
+            unit: None,
+        };
+    }
+
+    Fragment {
+        text: text.to_string(),
+        name: name.to_string(),
+        quantity,
+        unit,
+    }
+}
+
+fn split_quantity(text: &str) -> Option<(f64, &str)> {
+    let end = text
+        .find(|character: char| {
+            !(character.is_ascii_digit() || character == '.' || character == '/')
+        })
+        .unwrap_or(text.len());
+    let (number, rest) = text.split_at(end);
+
+    let quantity = match number.split_once('/') {
+        Some((numerator, denominator)) => {
+            let numerator: f64 = numerator.parse().ok()?;
+            let denominator: f64 = denominator.parse().ok()?;
+            if denominator == 0.0 {
+                return None;
+            }
+            numerator / denominator
+        }
+        None => number.parse().ok()?,
+    };
+
+    let separated = |rest: &str| rest.is_empty() || rest.starts_with(char::is_whitespace);
+
+    // "2x milk" counts the same as "2 milk", whereas "7up" is just a name
+    match rest.strip_prefix(['x', '×']) {
+        Some(stripped) if separated(stripped) => Some((quantity, stripped)),
+        _ if separated(rest) || split_unit(rest).is_some() => Some((quantity, rest)),
+        _ => None,
+    }
+}
+
+fn split_unit(text: &str) -> Option<(String, &str)> {
+    let text = text.trim_start();
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    let (word, rest) = text.split_at(end);
+    let word = word.trim_end_matches('.').to_lowercase();
+
+    if UNITS.contains(&word.as_str()) {
+        Some((word, rest))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fragment(text: &str, name: &str, quantity: Option<f64>, unit: Option<&str>) -> Fragment {
+        Fragment {
+            text: text.to_string(),
+            name: name.to_string(),
+            quantity,
+            unit: unit.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parse_mixed() {
+        assert_eq!(
+            parse("2 milk, bread, 500g minced beef, something for dessert"),
+            vec![
+                fragment("2 milk", "milk", Some(2.0), None),
+                fragment("bread", "bread", None, None),
+                fragment("500g minced beef", "minced beef", Some(500.0), Some("g")),
+                fragment("something for dessert", "something for dessert", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_separators() {
+        assert_eq!(
+            parse("eggs;\n butter ,, \n"),
+            vec![
+                fragment("eggs", "eggs", None, None),
+                fragment("butter", "butter", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_quantities() {
+        assert_eq!(
+            parse("1.5 kg potatoes, 1/2 cup sugar, 3x yoghurt, 2 L water"),
+            vec![
+                fragment("1.5 kg potatoes", "potatoes", Some(1.5), Some("kg")),
+                fragment("1/2 cup sugar", "sugar", Some(0.5), Some("cup")),
+                fragment("3x yoghurt", "yoghurt", Some(3.0), None),
+                fragment("2 L water", "water", Some(2.0), Some("l")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_names_starting_with_digits() {
+        assert_eq!(
+            parse("7up, 2 7up, 42"),
+            vec![
+                fragment("7up", "7up", None, None),
+                fragment("2 7up", "7up", Some(2.0), None),
+                fragment("42", "42", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_bare_units_as_names() {
+        assert_eq!(
+            parse("garlic, 2 cans, 500g, 1 kg."),
+            vec![
+                fragment("garlic", "garlic", None, None),
+                fragment("2 cans", "2 cans", None, None),
+                fragment("500g", "500g", None, None),
+                fragment("1 kg.", "1 kg.", None, None),
+            ]
+        );
+    }
+}
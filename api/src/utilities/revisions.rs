@@ -0,0 +1,307 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Change {
+    Added {
+        path: Vec<String>,
+        value: Value,
+    },
+    Removed {
+        path: Vec<String>,
+        value: Value,
+    },
+    Changed {
+        path: Vec<String>,
+        before: Value,
+        after: Value,
+    },
+    Moved {
+        path: Vec<String>,
+        from: usize,
+        to: usize,
+    },
+}
+
+// Elements of arrays are matched by the first of these keys they all have, or by index otherwise
+const IDENTITY_KEYS: [&str; 2] = ["link_id", "id"];
+
+pub fn diff(before: &Value, after: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_value(&mut Vec::new(), before, after, &mut changes);
+    changes
+}
+
+fn diff_value(path: &mut Vec<String>, before: &Value, after: &Value, changes: &mut Vec<Change>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => diff_object(path, before, after, changes),
+        (Value::Array(before), Value::Array(after)) => diff_array(path, before, after, changes),
+        _ if before != after => changes.push(Change::Changed {
+            path: path.clone(),
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn diff_object(
+    path: &mut Vec<String>,
+    before: &Map<String, Value>,
+    after: &Map<String, Value>,
+    changes: &mut Vec<Change>,
+) {
+    for (key, value) in before {
+        path.push(key.clone());
+        match after.get(key) {
+            Some(other) => diff_value(path, value, other, changes),
+            None => changes.push(Change::Removed {
+                path: path.clone(),
+                value: value.clone(),
+            }),
+        }
+        path.pop();
+    }
+
+    for (key, value) in after {
+        if !before.contains_key(key) {
+            path.push(key.clone());
+            changes.push(Change::Added {
+                path: path.clone(),
+                value: value.clone(),
+            });
+            path.pop();
+        }
+    }
+}
+
+fn diff_array(
+    path: &mut Vec<String>,
+    before: &[Value],
+    after: &[Value],
+    changes: &mut Vec<Change>,
+) {
+    let key = IDENTITY_KEYS.into_iter().find(|key| {
+        before
+            .iter()
+            .chain(after)
+            .all(|value| identity(value, key).is_some())
+    });
+
+    let key = match key {
+        Some(key) => key,
+        None => {
+            for (index, value) in before.iter().enumerate() {
+                path.push(index.to_string());
+                match after.get(index) {
+                    Some(other) => diff_value(path, value, other, changes),
+                    None => changes.push(Change::Removed {
+                        path: path.clone(),
+                        value: value.clone(),
+                    }),
+                }
+                path.pop();
+            }
+            for (index, value) in after.iter().enumerate().skip(before.len()) {
+                path.push(index.to_string());
+                changes.push(Change::Added {
+                    path: path.clone(),
+                    value: value.clone(),
+                });
+                path.pop();
+            }
+            return;
+        }
+    };
+
+    let position = |values: &[Value], id: &str| {
+        values
+            .iter()
+            .position(|value| identity(value, key) == Some(id))
+    };
+
+    // Elements only count as moved when their order relative to the other kept elements changed
+    let kept_before: Vec<&str> = before
+        .iter()
+        .filter_map(|value| identity(value, key))
+        .filter(|id| position(after, id).is_some())
+        .collect();
+    let kept_after: Vec<&str> = after
+        .iter()
+        .filter_map(|value| identity(value, key))
+        .filter(|id| position(before, id).is_some())
+        .collect();
+
+    for (from, value) in before.iter().enumerate() {
+        let id = identity(value, key).unwrap_or_default();
+        path.push(id.to_string());
+        match position(after, id) {
+            Some(to) => {
+                let kept_from = kept_before.iter().position(|kept| *kept == id);
+                let kept_to = kept_after.iter().position(|kept| *kept == id);
+                if kept_from != kept_to {
+                    changes.push(Change::Moved {
+                        path: path.clone(),
+                        from,
+                        to,
+                    });
+                }
+                diff_value(path, value, &after[to], changes);
+            }
+            None => changes.push(Change::Removed {
+                path: path.clone(),
+                value: value.clone(),
+            }),
+        }
+        path.pop();
+    }
+
+    for value in after {
+        let id = identity(value, key).unwrap_or_default();
+        if position(before, id).is_none() {
+            path.push(id.to_string());
+            changes.push(Change::Added {
+                path: path.clone(),
+                value: value.clone(),
+            });
+            path.pop();
+        }
+    }
+}
+
+fn identity<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key)?.as_str()
+}
+
+// Contents missing from a revision are removed when restoring it, unless they are still in use
+// elsewhere, in which case the first of those is returned
+pub fn removals<K: Copy + PartialEq>(current: &[(K, bool)], kept: &[K]) -> Result<Vec<K>, K> {
+    let mut removed = Vec::new();
+
+    for (id, in_use) in current {
+        if kept.contains(id) {
+            continue;
+        }
+        if *in_use {
+            return Err(*id);
+        }
+        removed.push(*id);
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(|segment| segment.to_string()).collect()
+    }
+
+    #[test]
+    fn objects() {
+        let before = json!({ "name": "Bread", "recipe": { "servings": 2 }, "cuisine": "Dutch" });
+        let after = json!({ "name": "Bread", "recipe": { "servings": 4 }, "author": "Anna" });
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                Change::Removed {
+                    path: path(&["cuisine"]),
+                    value: json!("Dutch")
+                },
+                Change::Changed {
+                    path: path(&["recipe", "servings"]),
+                    before: json!(2),
+                    after: json!(4)
+                },
+                Change::Added {
+                    path: path(&["author"]),
+                    value: json!("Anna")
+                },
+            ]
+        );
+        assert!(diff(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn arrays_by_identity() {
+        let before = json!([
+            { "link_id": "a", "id": "x", "text": "Flour" },
+            { "link_id": "b", "id": "y", "text": "Water" },
+            { "link_id": "c", "id": "z", "text": "Salt" },
+        ]);
+        let after = json!([
+            { "link_id": "c", "id": "z", "text": "Salt" },
+            { "link_id": "a", "id": "x", "text": "Rye flour" },
+            { "link_id": "d", "id": "x", "text": "Yeast" },
+        ]);
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                Change::Moved {
+                    path: path(&["a"]),
+                    from: 0,
+                    to: 1
+                },
+                Change::Changed {
+                    path: path(&["a", "text"]),
+                    before: json!("Flour"),
+                    after: json!("Rye flour")
+                },
+                Change::Removed {
+                    path: path(&["b"]),
+                    value: json!({ "link_id": "b", "id": "y", "text": "Water" })
+                },
+                Change::Moved {
+                    path: path(&["c"]),
+                    from: 2,
+                    to: 0
+                },
+                Change::Added {
+                    path: path(&["d"]),
+                    value: json!({ "link_id": "d", "id": "x", "text": "Yeast" })
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn arrays_by_index() {
+        let before = json!({ "rows": [[1, 2], [3, 4]] });
+        let after = json!({ "rows": [[1, 5]] });
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                Change::Changed {
+                    path: path(&["rows", "0", "1"]),
+                    before: json!(2),
+                    after: json!(5)
+                },
+                Change::Removed {
+                    path: path(&["rows", "1"]),
+                    value: json!([3, 4])
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn removals_of_unused_contents() {
+        assert_eq!(
+            removals(&[(1, false), (2, true), (3, false)], &[2]),
+            Ok(vec![1, 3])
+        );
+    }
+
+    #[test]
+    fn removals_of_contents_in_use() {
+        // An ingredient added after the revision is on a list
+        assert_eq!(removals(&[(1, false), (2, true)], &[1]), Err(2));
+    }
+}
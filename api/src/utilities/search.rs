@@ -0,0 +1,46 @@
+use pulldown_cmark_escape::escape_html;
+
+// Postgres marks matches in snippets with these, as they can not occur in regular text
+pub const HIGHLIGHT_START: char = '\u{E000}';
+pub const HIGHLIGHT_STOP: char = '\u{E001}';
+
+pub fn snippet_to_html(snippet: &str) -> String {
+    let mut html = String::new();
+    // Writing into a string cannot fail
+    let _ = escape_html(&mut html, snippet);
+
+    html.replace(HIGHLIGHT_START, "<mark>")
+        .replace(HIGHLIGHT_STOP, "</mark>")
+}
+
+// Makes user input match literally in LIKE patterns
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snippets() {
+        assert_eq!(
+            snippet_to_html("Add the \u{E000}cardamom\u{E001} <b>now</b> & stir"),
+            "Add the <mark>cardamom</mark> &lt;b&gt;now&lt;/b&gt; &amp; stir"
+        );
+    }
+
+    #[test]
+    fn like_patterns() {
+        assert_eq!(escape_like("crème fraîche"), "crème fraîche");
+        assert_eq!(escape_like("100%_a\\b"), "100\\%\\_a\\\\b");
+    }
+}
@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::Hash;
+
+use pulldown_cmark_escape::escape_html;
+use serde::{Deserialize, Serialize};
+
+#[derive(sqlx::Type, Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[sqlx(type_name = "table_alignment", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn css(&self) -> &'static str {
+        match self {
+            Alignment::Left => "left",
+            Alignment::Center => "center",
+            Alignment::Right => "right",
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Column {
+    #[serde(default)]
+    pub header: String,
+    #[serde(default)]
+    pub alignment: Alignment,
+}
+
+// Cells are plain JSON strings or numbers, empty cells are null
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Cell {
+    Number(f64),
+    Text(String),
+}
+
+pub type Row = Vec<Option<Cell>>;
+
+pub fn validate(columns: &[Column], rows: &[Row]) -> Result<(), TableError> {
+    match rows.iter().find(|row| row.len() != columns.len()) {
+        Some(row) => Err(TableError::CellCount {
+            expected: columns.len(),
+            actual: row.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+// Rows are appended when no position is given and filled with empty cells when none are given
+pub fn insert_row(
+    columns: &[Column],
+    rows: &mut Vec<Row>,
+    position: Option<usize>,
+    cells: Option<Row>,
+) -> Result<(), TableError> {
+    let position = checked_position(position, rows.len())?;
+    let row = cells.unwrap_or_else(|| vec![None; columns.len()]);
+    validate(columns, std::slice::from_ref(&row))?;
+
+    rows.insert(position, row);
+
+    Ok(())
+}
+
+pub fn delete_row(rows: &mut Vec<Row>, index: usize) -> Result<(), TableError> {
+    if index >= rows.len() {
+        return Err(TableError::OutOfRange);
+    }
+
+    rows.remove(index);
+
+    Ok(())
+}
+
+// Columns are appended when no position is given and filled with empty cells when none are given
+pub fn insert_column(
+    columns: &mut Vec<Column>,
+    rows: &mut [Row],
+    position: Option<usize>,
+    column: Column,
+    cells: Option<Vec<Option<Cell>>>,
+) -> Result<(), TableError> {
+    let position = checked_position(position, columns.len())?;
+    let cells = cells.unwrap_or_else(|| vec![None; rows.len()]);
+    if cells.len() != rows.len() {
+        return Err(TableError::CellCount {
+            expected: rows.len(),
+            actual: cells.len(),
+        });
+    }
+
+    columns.insert(position, column);
+    for (row, cell) in rows.iter_mut().zip(cells) {
+        row.insert(position, cell);
+    }
+
+    Ok(())
+}
+
+pub fn delete_column(
+    columns: &mut Vec<Column>,
+    rows: &mut [Row],
+    index: usize,
+) -> Result<(), TableError> {
+    if index >= columns.len() {
+        return Err(TableError::OutOfRange);
+    }
+
+    columns.remove(index);
+    for row in rows {
+        row.remove(index);
+    }
+
+    Ok(())
+}
+
+// Cells come per table and row in column order, with no cell for tables without columns
+pub fn collect_rows<K>(
+    cells: impl IntoIterator<Item = (K, K, Option<Option<Cell>>)>,
+) -> HashMap<K, Vec<Row>>
+where
+    K: Copy + Eq + Hash,
+{
+    let mut tables: HashMap<K, Vec<Row>> = HashMap::new();
+    let mut current: HashMap<K, K> = HashMap::new();
+
+    for (table_id, row_id, cell) in cells {
+        let rows = tables.entry(table_id).or_default();
+        if current.insert(table_id, row_id) != Some(row_id) {
+            rows.push(Vec::new());
+        }
+
+        if let (Some(cell), Some(cells)) = (cell, rows.last_mut()) {
+            cells.push(cell);
+        }
+    }
+
+    tables
+}
+
+fn checked_position(position: Option<usize>, len: usize) -> Result<usize, TableError> {
+    match position {
+        Some(position) if position > len => Err(TableError::OutOfRange),
+        Some(position) => Ok(position),
+        None => Ok(len),
+    }
+}
+
+pub fn table_to_html(columns: &[Column], rows: &[Row]) -> String {
+    let mut html = String::from("<table>\n<thead>\n<tr>\n");
+
+    for column in columns {
+        push_cell(&mut html, "th", column.alignment, &column.header);
+    }
+
+    html.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for row in rows {
+        html.push_str("<tr>\n");
+        for (column, cell) in columns.iter().zip(row) {
+            let text = match cell {
+                Some(Cell::Number(number)) => number.to_string(),
+                Some(Cell::Text(text)) => text.clone(),
+                None => String::new(),
+            };
+            push_cell(&mut html, "td", column.alignment, &text);
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str("</tbody>\n</table>\n");
+
+    html
+}
+
+fn push_cell(html: &mut String, tag: &str, alignment: Alignment, text: &str) {
+    html.push_str(&format!(
+        "<{} style=\"text-align: {}\">",
+        tag,
+        alignment.css()
+    ));
+    // Writing into a string cannot fail
+    let _ = escape_html(&mut *html, text);
+    html.push_str(&format!("</{}>\n", tag));
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableError {
+    OutOfRange,
+    CellCount { expected: usize, actual: usize },
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TableError::OutOfRange => write!(f, "row or column index is out of range"),
+            TableError::CellCount { expected, actual } => {
+                write!(f, "expected {} cells but got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl Error for TableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> Option<Cell> {
+        Some(Cell::Text(text.to_string()))
+    }
+
+    fn sample() -> (Vec<Column>, Vec<Row>) {
+        (
+            vec![
+                Column {
+                    header: "Ingredient".to_string(),
+                    alignment: Alignment::Left,
+                },
+                Column {
+                    header: "Grams".to_string(),
+                    alignment: Alignment::Right,
+                },
+            ],
+            vec![
+                vec![text("Flour"), Some(Cell::Number(500.0))],
+                vec![text("Water"), Some(Cell::Number(350.0))],
+            ],
+        )
+    }
+
+    #[test]
+    fn cells_deserialize() {
+        let row: Row = serde_json::from_str(r#"["Salt", 2.5, null]"#).unwrap();
+
+        assert_eq!(row, vec![text("Salt"), Some(Cell::Number(2.5)), None]);
+    }
+
+    #[test]
+    fn rows() {
+        let (columns, mut rows) = sample();
+
+        insert_row(&columns, &mut rows, Some(1), None).unwrap();
+        insert_row(&columns, &mut rows, None, Some(vec![text("Salt"), None])).unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1], vec![None, None]);
+        assert_eq!(rows[3][0], text("Salt"));
+
+        delete_row(&mut rows, 1).unwrap();
+        assert_eq!(rows[1][0], text("Water"));
+
+        assert_eq!(
+            insert_row(&columns, &mut rows, Some(4), None),
+            Err(TableError::OutOfRange)
+        );
+        assert_eq!(
+            insert_row(&columns, &mut rows, None, Some(vec![text("Yeast")])),
+            Err(TableError::CellCount {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(delete_row(&mut rows, 3), Err(TableError::OutOfRange));
+    }
+
+    #[test]
+    fn columns() {
+        let (mut columns, mut rows) = sample();
+
+        insert_column(
+            &mut columns,
+            &mut rows,
+            Some(1),
+            Column {
+                header: "Percentage".to_string(),
+                alignment: Alignment::Center,
+            },
+            Some(vec![Some(Cell::Number(100.0)), Some(Cell::Number(70.0))]),
+        )
+        .unwrap();
+        assert_eq!(columns[1].header, "Percentage");
+        assert_eq!(
+            rows[1],
+            vec![
+                text("Water"),
+                Some(Cell::Number(70.0)),
+                Some(Cell::Number(350.0))
+            ]
+        );
+
+        delete_column(&mut columns, &mut rows, 0).unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(
+            rows[0],
+            vec![Some(Cell::Number(100.0)), Some(Cell::Number(500.0))]
+        );
+        assert!(validate(&columns, &rows).is_ok());
+
+        assert_eq!(
+            insert_column(
+                &mut columns,
+                &mut rows,
+                None,
+                Column::default(),
+                Some(vec![None])
+            ),
+            Err(TableError::CellCount {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            delete_column(&mut columns, &mut rows, 2),
+            Err(TableError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn rows_of_several_tables() {
+        let cells = vec![
+            (1, 10, Some(text("Flour"))),
+            (1, 10, Some(Some(Cell::Number(500.0)))),
+            (2, 20, Some(text("Oven"))),
+            (1, 11, Some(text("Water"))),
+            (1, 11, Some(None)),
+            (3, 30, None),
+            (2, 21, Some(text("Grill"))),
+        ];
+
+        let tables = collect_rows(cells);
+        assert_eq!(
+            tables[&1],
+            vec![
+                vec![text("Flour"), Some(Cell::Number(500.0))],
+                vec![text("Water"), None]
+            ]
+        );
+        assert_eq!(tables[&2], vec![vec![text("Oven")], vec![text("Grill")]]);
+        assert_eq!(tables[&3], vec![Vec::new()]);
+    }
+
+    #[test]
+    fn html() {
+        let (columns, mut rows) = sample();
+        rows[1][0] = text("<Water & co>");
+        rows[1][1] = None;
+
+        assert_eq!(
+            table_to_html(&columns, &rows),
+            "<table>\n<thead>\n<tr>\n\
+             <th style=\"text-align: left\">Ingredient</th>\n\
+             <th style=\"text-align: right\">Grams</th>\n\
+             </tr>\n</thead>\n<tbody>\n\
+             <tr>\n\
+             <td style=\"text-align: left\">Flour</td>\n\
+             <td style=\"text-align: right\">500</td>\n\
+             </tr>\n\
+             <tr>\n\
+             <td style=\"text-align: left\">&lt;Water &amp; co&gt;</td>\n\
+             <td style=\"text-align: right\"></td>\n\
+             </tr>\n\
+             </tbody>\n</table>\n"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+import { get, host, type DataParams, type DataResponse } from '..';
+
+function url(searchParams: URLSearchParams = new URLSearchParams()) {
+	searchParams = new URLSearchParams(
+		[...searchParams.entries()].filter(([key]) => ['q', 'types', 'take'].includes(key))
+	);
+
+	if (searchParams.size > 0) {
+		return `${host}/api/autocomplete?${searchParams.toString()}`;
+	} else {
+		return `${host}/api/autocomplete`;
+	}
+}
+
+export type SuggestionType = 'product' | 'page' | 'list';
+
+export type GetResponse = {
+	data: {
+		type: SuggestionType;
+		id: string;
+		name: string;
+		rank: number;
+	}[];
+};
+
+export default {
+	url: (searchParams?: URLSearchParams) => {
+		return url(searchParams);
+	},
+
+	get: (searchParams?: URLSearchParams, params?: DataParams): Promise<DataResponse<GetResponse>> => {
+		return get(url(searchParams), params);
+	}
+};
@@ -4,6 +4,14 @@ function url() {
 	return `${host}/api/blocks`;
 }
 
+export type Step = {
+	text: string;
+	duration?: number;
+	ingredients: {
+		id: string;
+	}[];
+};
+
 export type GetResponse = {
 	data: {
 		id: string;
@@ -22,6 +30,26 @@ export type GetResponse = {
 				| {
 						type: 'recipe_collection';
 						id: string;
+				  }
+				| {
+						type: 'steps';
+						steps: Step[];
+				  }
+				| {
+						type: 'page_reference';
+						id: string;
+						data?: {
+							type: string;
+							name: string;
+						};
+				  }
+				| {
+						type: 'checklist';
+						id: string;
+				  }
+				| {
+						type: 'table';
+						id: string;
 				  };
 		};
 	}[];
@@ -37,6 +65,22 @@ export type PostRequest = {
 			| {
 					type: 'recipe_collection';
 					id: string;
+			  }
+			| {
+					type: 'steps';
+					steps: Step[];
+			  }
+			| {
+					type: 'page_reference';
+					id: string;
+			  }
+			| {
+					type: 'checklist';
+					id: string;
+			  }
+			| {
+					type: 'table';
+					id: string;
 			  };
 	}[];
 };
@@ -51,6 +95,22 @@ export type PostResponse = {
 			| {
 					type: 'recipe_collection';
 					id: string;
+			  }
+			| {
+					type: 'steps';
+					steps: Step[];
+			  }
+			| {
+					type: 'page_reference';
+					id: string;
+			  }
+			| {
+					type: 'checklist';
+					id: string;
+			  }
+			| {
+					type: 'table';
+					id: string;
 			  };
 	}[];
 };
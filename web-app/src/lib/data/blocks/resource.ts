@@ -1,4 +1,5 @@
 import { del, get, host, patch, type DataParams, type DataResponse } from '..';
+import type { Step } from './collection';
 
 function url(id: string) {
 	return `${host}/api/blocks/${id}`;
@@ -21,6 +22,10 @@ export type GetResponse = {
 			| {
 					type: 'recipe_collection';
 					id: string;
+			  }
+			| {
+					type: 'steps';
+					steps: Step[];
 			  };
 	};
 };
@@ -34,6 +39,10 @@ export type PatchRequest = {
 		| {
 				type: 'recipe_collection';
 				id: string;
+		  }
+		| {
+				type: 'steps';
+				steps: Step[];
 		  };
 };
 
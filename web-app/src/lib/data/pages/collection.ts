@@ -2,7 +2,7 @@ import { get, host, post, type DataParams, type DataResponse } from '..';
 
 function url(searchParams: URLSearchParams = new URLSearchParams()) {
 	searchParams = new URLSearchParams(
-		[...searchParams.entries()].filter(([key]) => ['type'].includes(key))
+		[...searchParams.entries()].filter(([key]) => ['name', 'type', 'max_total_time', 'difficulty', 'cuisine', 'author'].includes(key))
 	);
 
 	if (searchParams.size > 0) {
@@ -12,7 +12,21 @@ function url(searchParams: URLSearchParams = new URLSearchParams()) {
 	}
 }
 
-export type PageType = 'recipe';
+export type PageType = 'recipe' | 'note' | 'article' | 'meal_plan';
+
+export type RecipeDifficulty = 'easy' | 'medium' | 'hard';
+
+export type RecipeMetadata = {
+	prep_time?: number;
+	cook_time?: number;
+	total_time?: number;
+	servings?: number;
+	servings_unit?: string;
+	difficulty?: RecipeDifficulty;
+	cuisine?: string;
+	source_url?: string;
+	source_author?: string;
+};
 
 export type GetResponse = {
 	data: {
@@ -25,6 +39,7 @@ export type GetResponse = {
 			blocks: {
 				id: string;
 			}[];
+			recipe?: RecipeMetadata;
 		};
 	}[];
 };
@@ -36,6 +51,7 @@ export type PostRequest = {
 		blocks: {
 			id: string;
 		}[];
+		recipe?: RecipeMetadata;
 	}[];
 };
 
@@ -1,5 +1,5 @@
-import { del, get, host, patch, type DataParams, type DataResponse } from '..';
-import type { PageType } from './collection';
+import { del, get, host, patch, post, type DataParams, type DataResponse } from '..';
+import type { PageType, RecipeMetadata } from './collection';
 
 function url(id: string) {
 	return `${host}/api/pages/${id}`;
@@ -13,6 +13,7 @@ export type GetResponse = {
 		type: PageType;
 		name: string;
 		blocks: {
+			link_id: string;
 			id: string;
 			data: {
 				kind:
@@ -52,6 +53,7 @@ export type GetResponse = {
 					  };
 			};
 		}[];
+		recipe?: RecipeMetadata;
 	};
 };
 
@@ -61,6 +63,48 @@ export type PatchRequest = {
 	blocks: {
 		id: string;
 	}[];
+	recipe?: RecipeMetadata | null;
+};
+
+export type AddToListRequest = {
+	list_id: string;
+};
+
+export type AddToListResponse = {
+	data: {
+		id: string;
+	}[];
+};
+
+export type InsertBlockRequest = (
+	| {
+			block_id: string;
+	  }
+	| {
+			kind:
+				| {
+						type: 'markdown';
+						markdown: string;
+				  }
+				| {
+						type: 'ingredient_collection';
+						ingredients: {
+							product: {
+								id: string;
+							};
+							quantity?: number;
+							unit?: string;
+						}[];
+				  };
+	  }
+) & {
+	position?: number;
+};
+
+export type InsertBlockResponse = GetResponse['data']['blocks'][number];
+
+export type MoveBlockRequest = {
+	position: number;
 };
 
 export default {
@@ -91,5 +135,34 @@ export default {
 
 	delete: (id: string, params?: DataParams): Promise<DataResponse<void>> => {
 		return del(url(id), params);
+	},
+
+	addToList: (
+		id: string,
+		body: AddToListRequest,
+		params?: DataParams
+	): Promise<DataResponse<AddToListResponse>> => {
+		return post(`${url(id)}/add-to-list`, body, params);
+	},
+
+	insertBlock: (
+		id: string,
+		body: InsertBlockRequest,
+		params?: DataParams
+	): Promise<DataResponse<InsertBlockResponse>> => {
+		return post(`${url(id)}/blocks`, body, params);
+	},
+
+	moveBlock: (
+		id: string,
+		linkId: string,
+		body: MoveBlockRequest,
+		params?: DataParams
+	): Promise<DataResponse<void>> => {
+		return patch(`${url(id)}/blocks/${linkId}`, body, params);
+	},
+
+	removeBlock: (id: string, linkId: string, params?: DataParams): Promise<DataResponse<void>> => {
+		return del(`${url(id)}/blocks/${linkId}`, params);
 	}
 };
@@ -0,0 +1,69 @@
+import { get, host, post, type DataParams, type DataResponse } from '..';
+import type { GetResponse as PageResponse } from './resource';
+
+function url(id: string, revisionId?: string) {
+	const base = `${host}/api/pages/${id}/revisions`;
+	return revisionId ? `${base}/${revisionId}` : base;
+}
+
+export type Revision = {
+	id: string;
+	ts_created: Date;
+	data: {
+		number: number;
+	};
+};
+
+export type GetCollectionResponse = {
+	data: Revision[];
+};
+
+export type GetResponse = Revision & {
+	data: {
+		snapshot: PageResponse;
+	};
+};
+
+export type Change =
+	| { op: 'added'; path: string[]; value: unknown }
+	| { op: 'removed'; path: string[]; value: unknown }
+	| { op: 'changed'; path: string[]; before: unknown; after: unknown }
+	| { op: 'moved'; path: string[]; from: number; to: number };
+
+export type DiffResponse = {
+	from: Revision;
+	to: Revision;
+	changes: Change[];
+};
+
+export default {
+	url: (id: string, revisionId?: string) => {
+		return url(id, revisionId);
+	},
+
+	getCollection: (id: string, params?: DataParams): Promise<DataResponse<GetCollectionResponse>> => {
+		return get(url(id), params);
+	},
+
+	get: (id: string, revisionId: string, params?: DataParams): Promise<DataResponse<GetResponse>> => {
+		return get(url(id, revisionId), params);
+	},
+
+	diff: (
+		id: string,
+		revisionId: string,
+		to: string,
+		params?: DataParams
+	): Promise<DataResponse<DiffResponse>> => {
+		const searchParams = new URLSearchParams({ to });
+		return get(`${url(id, revisionId)}/diff?${searchParams}`, params);
+	},
+
+	restore: (
+		id: string,
+		revisionId: string,
+		params?: DataParams
+	): Promise<DataResponse<PageResponse>> => {
+		return post(`${url(id, revisionId)}/restore`, {}, params);
+	}
+};
@@ -0,0 +1,40 @@
+import { get, host, type DataParams, type DataResponse } from '..';
+import type { PageType } from '../pages/collection';
+
+function url(searchParams: URLSearchParams = new URLSearchParams()) {
+	searchParams = new URLSearchParams(
+		[...searchParams.entries()].filter(([key]) => ['q', 'take'].includes(key))
+	);
+
+	if (searchParams.size > 0) {
+		return `${host}/api/search?${searchParams.toString()}`;
+	} else {
+		return `${host}/api/search`;
+	}
+}
+
+export type GetResponse = {
+	data: {
+		page: {
+			id: string;
+			data: {
+				type: PageType;
+				name: string;
+			};
+		};
+		rank: number;
+		block_ids: string[];
+		// HTML with matches wrapped in mark elements
+		snippets: string[];
+	}[];
+};
+
+export default {
+	url: (searchParams?: URLSearchParams) => {
+		return url(searchParams);
+	},
+
+	get: (searchParams?: URLSearchParams, params?: DataParams): Promise<DataResponse<GetResponse>> => {
+		return get(url(searchParams), params);
+	}
+};